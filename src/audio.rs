@@ -1,9 +1,31 @@
-use bevy::prelude::*;
+use bevy::{
+    audio::{AudioSinkPlayback, SpatialScale, Volume},
+    ecs::{component::Mutable, system::SystemParam},
+    prelude::*,
+};
+use serde::{Deserialize, Serialize};
 
 pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<MusicVolume>();
+    app.init_resource::<SoundEffectVolume>();
+    app.init_resource::<UiVolume>();
+
     app.add_systems(
         Update,
-        apply_global_volume.run_if(resource_changed::<GlobalVolume>),
+        (
+            (
+                apply_global_volume::<AudioSink>,
+                apply_global_volume::<SpatialAudioSink>,
+            )
+                .run_if(
+                    resource_changed::<GlobalVolume>
+                        .or(resource_changed::<MusicVolume>)
+                        .or(resource_changed::<SoundEffectVolume>)
+                        .or(resource_changed::<UiVolume>),
+                ),
+            apply_volume_to_changed_sinks::<AudioSink>,
+            apply_volume_to_changed_sinks::<SpatialAudioSink>,
+        ),
     );
 }
 
@@ -33,12 +55,165 @@ pub fn sound_effect(handle: Handle<AudioSource>) -> impl Bundle {
     (AudioPlayer(handle), PlaybackSettings::DESPAWN, SoundEffect)
 }
 
-/// [`GlobalVolume`] doesn't apply to already-running audio entities, so this system will update them.
-fn apply_global_volume(
-    global_volume: Res<GlobalVolume>,
-    mut audio_query: Query<(&PlaybackSettings, &mut AudioSink)>,
+/// A sound effect audio instance with its own volume and playback speed, which also changes
+/// its pitch.
+pub fn varied_sound_effect(handle: Handle<AudioSource>, volume: Volume, speed: f32) -> impl Bundle {
+    (
+        AudioPlayer(handle),
+        PlaybackSettings::DESPAWN
+            .with_volume(volume)
+            .with_speed(speed),
+        SoundEffect,
+    )
+}
+
+/// How far apart the ears of the [`SpatialListener`] are, in world units. Sounds pan gradually
+/// from one side of the arena to the other.
+pub const LISTENER_EAR_GAP: f32 = 640.;
+
+/// Spatial sounds closer than this many world units to an ear play at full volume in that ear,
+/// and get quieter with the square of the distance beyond it.
+const SPATIAL_FULL_VOLUME_DISTANCE: f32 = 500.;
+
+/// A sound effect audio instance like [`varied_sound_effect`], panned and attenuated by where
+/// `position` is relative to the [`SpatialListener`].
+pub fn spatial_sound_effect(
+    handle: Handle<AudioSource>,
+    position: Vec2,
+    volume: Volume,
+    speed: f32,
+) -> impl Bundle {
+    (
+        AudioPlayer(handle),
+        PlaybackSettings::DESPAWN
+            .with_volume(volume)
+            .with_speed(speed)
+            .with_spatial(true)
+            .with_spatial_scale(SpatialScale::new_2d(1. / SPATIAL_FULL_VOLUME_DISTANCE)),
+        Transform::from_translation(position.extend(0.)),
+        SoundEffect,
+    )
+}
+
+/// An organizational marker component that should be added to a spawned [`AudioPlayer`] if it's in the
+/// "UI sound" category (e.g. button hovers and clicks).
+///
+/// This can then be used to query for and operate on sounds in that category.
+#[derive(Component, Reflect, Default)]
+#[reflect(Component)]
+pub struct UiSound;
+
+/// A UI sound audio instance.
+pub fn ui_sound(handle: Handle<AudioSource>) -> impl Bundle {
+    (AudioPlayer(handle), PlaybackSettings::DESPAWN, UiSound)
+}
+
+/// The volume of one category of audio, applied on top of [`GlobalVolume`].
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, Reflect)]
+#[serde(default)]
+pub struct CategoryVolume {
+    /// Linear volume.
+    pub volume: f32,
+    pub muted: bool,
+}
+
+impl Default for CategoryVolume {
+    fn default() -> Self {
+        Self {
+            volume: 1.0,
+            muted: false,
+        }
+    }
+}
+
+impl CategoryVolume {
+    fn get(self) -> Volume {
+        if self.muted {
+            Volume::SILENT
+        } else {
+            Volume::Linear(self.volume)
+        }
+    }
+}
+
+/// The volume of [`Music`].
+#[derive(Resource, Clone, Copy, Debug, Default, Reflect)]
+#[reflect(Resource)]
+pub struct MusicVolume(pub CategoryVolume);
+
+/// The volume of [`SoundEffect`]s.
+#[derive(Resource, Clone, Copy, Debug, Default, Reflect)]
+#[reflect(Resource)]
+pub struct SoundEffectVolume(pub CategoryVolume);
+
+/// The volume of [`UiSound`]s.
+#[derive(Resource, Clone, Copy, Debug, Default, Reflect)]
+#[reflect(Resource)]
+pub struct UiVolume(pub CategoryVolume);
+
+/// Scales the volume of an audio entity on top of its category, e.g. to fade it in or out.
+#[derive(Component, Clone, Copy, Debug, Reflect)]
+#[reflect(Component)]
+pub struct VolumeScale(pub f32);
+
+/// The volume of every category of audio, combined with [`GlobalVolume`].
+#[derive(SystemParam)]
+struct Volumes<'w> {
+    global: Res<'w, GlobalVolume>,
+    music: Res<'w, MusicVolume>,
+    sound_effects: Res<'w, SoundEffectVolume>,
+    ui: Res<'w, UiVolume>,
+}
+
+impl Volumes<'_> {
+    /// The volume a sink should play at.
+    fn of(
+        &self,
+        playback: &PlaybackSettings,
+        (music, sound_effect, ui, scale): (bool, bool, bool, Option<&VolumeScale>),
+    ) -> Volume {
+        let category = if music {
+            self.music.0.get()
+        } else if sound_effect {
+            self.sound_effects.0.get()
+        } else if ui {
+            self.ui.0.get()
+        } else {
+            Volume::Linear(1.0)
+        };
+        let scale = Volume::Linear(scale.map_or(1.0, |scale| scale.0));
+        self.global.volume * category * scale * playback.volume
+    }
+}
+
+type AudioCategory = (
+    Has<Music>,
+    Has<SoundEffect>,
+    Has<UiSound>,
+    Option<&'static VolumeScale>,
+);
+
+/// [`GlobalVolume`] and category volumes don't apply to already-running audio entities, so this
+/// system will update them.
+fn apply_global_volume<Sink: Component<Mutability = Mutable> + AudioSinkPlayback>(
+    volumes: Volumes,
+    mut audio_query: Query<(&PlaybackSettings, &mut Sink, AudioCategory)>,
+) {
+    for (playback, mut sink, category) in &mut audio_query {
+        sink.set_volume(volumes.of(playback, category));
+    }
+}
+
+/// New audio entities only get [`GlobalVolume`] applied, so add the rest, and keep fading audio
+/// up to date.
+fn apply_volume_to_changed_sinks<Sink: Component<Mutability = Mutable> + AudioSinkPlayback>(
+    volumes: Volumes,
+    mut audio_query: Query<
+        (&PlaybackSettings, &mut Sink, AudioCategory),
+        Or<(Added<Sink>, Changed<VolumeScale>)>,
+    >,
 ) {
-    for (playback, mut sink) in &mut audio_query {
-        sink.set_volume(global_volume.volume * playback.volume);
+    for (playback, mut sink, category) in &mut audio_query {
+        sink.set_volume(volumes.of(playback, category));
     }
 }
@@ -0,0 +1,279 @@
+//! Special abilities, used with [`PlayerAction::Ability`].
+//!
+//! Abilities are defined in `assets/abilities/default.abilities.ron`. Every player picks one
+//! in the lobby, and can use it again once its cooldown has run out.
+
+use avian2d::prelude::*;
+use bevy::prelude::*;
+use leafwing_input_manager::prelude::*;
+use serde::Deserialize;
+
+use crate::{
+    AppSystems, PausableSystems,
+    asset_tracking::{LoadResource, RonAssetLoader},
+    duel::{
+        arena::CurrentArena,
+        control::PlayerAction,
+        guard::Stunned,
+        player::{PlayerID, PlayerPart, PlayerParts},
+        tuning::Tuning,
+        weapon::WeaponBody,
+    },
+    screens::Screen,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_asset::<AbilitySet>();
+    app.register_asset_loader(RonAssetLoader::<AbilitySet>::new(&["abilities.ron"]));
+    app.load_resource::<AbilityAssets>();
+
+    app.add_systems(
+        Update,
+        (
+            tick_abilities.in_set(AppSystems::TickTimers),
+            use_abilities.in_set(AppSystems::RecordInput),
+        )
+            .in_set(PausableSystems)
+            .run_if(in_state(Screen::Gameplay)),
+    );
+}
+
+/// Every ability players can pick from.
+#[derive(Asset, Clone, Debug, Deserialize, Reflect)]
+pub struct AbilitySet {
+    pub abilities: Vec<AbilityDef>,
+}
+
+#[derive(Clone, Debug, Deserialize, Reflect)]
+pub struct AbilityDef {
+    pub name: String,
+    pub cooldown_secs: f32,
+    pub effect: AbilityEffect,
+}
+
+/// What an ability does. Speeds are velocity changes, independent of the mass they're
+/// applied to.
+#[derive(Clone, Copy, Debug, Deserialize, Reflect)]
+pub enum AbilityEffect {
+    /// Slam downwards, knocking away nearby players.
+    GroundSlam {
+        speed: f32,
+        radius: f32,
+        /// Knockback speed right next to the player, falling off towards `radius`.
+        knockback: f32,
+    },
+    /// Whip the weapon around the player in the direction it's already swinging.
+    WeaponSpin { speed: f32 },
+    /// Jump a short distance in the direction the player is moving, weapon and all. Stops short
+    /// of anything solid in the way and of the arena bounds.
+    Teleport { distance: f32 },
+    /// Pull nearby enemy weapons towards the player.
+    Magnet { radius: f32, speed: f32 },
+}
+
+#[derive(Resource, Asset, Clone, Reflect)]
+#[reflect(Resource)]
+pub struct AbilityAssets {
+    #[dependency]
+    set: Handle<AbilitySet>,
+}
+
+impl AbilityAssets {
+    /// All abilities, or none if the ability set has been unloaded.
+    pub fn abilities<'a>(&self, sets: &'a Assets<AbilitySet>) -> &'a [AbilityDef] {
+        sets.get(&self.set)
+            .map(|set| set.abilities.as_slice())
+            .unwrap_or_default()
+    }
+}
+
+impl FromWorld for AbilityAssets {
+    fn from_world(world: &mut World) -> Self {
+        let assets = world.resource::<AssetServer>();
+        Self {
+            set: assets.load("abilities/default.abilities.ron"),
+        }
+    }
+}
+
+/// A player's special ability.
+#[derive(Component, Debug, Clone)]
+pub struct Ability {
+    pub def: AbilityDef,
+    /// Time until the ability can be used again.
+    pub cooldown: Timer,
+}
+
+impl Ability {
+    /// An ability that's ready to use.
+    pub fn new(def: AbilityDef) -> Self {
+        let mut cooldown = Timer::from_seconds(def.cooldown_secs, TimerMode::Once);
+        cooldown.finish();
+        Self { def, cooldown }
+    }
+}
+
+fn tick_abilities(time: Res<Time>, mut abilities: Query<&mut Ability>) {
+    for mut ability in &mut abilities {
+        ability.cooldown.tick(time.delta());
+    }
+}
+
+fn use_abilities(
+    mut casters: Query<
+        (
+            Entity,
+            &ActionState<PlayerAction>,
+            &mut Ability,
+            &PlayerParts,
+        ),
+        (With<PlayerID>, Without<Stunned>),
+    >,
+    players: Query<Entity, With<PlayerID>>,
+    weapons: Query<(Entity, &PlayerPart), With<WeaponBody>>,
+    mut bodies: ParamSet<(Query<(Forces, &ComputedMass)>, Query<&mut Position>)>,
+    spatial_query: SpatialQuery,
+    colliders: Query<&ColliderOf>,
+    sensors: Query<(), With<Sensor>>,
+    arena: Res<CurrentArena>,
+    tuning: Res<Tuning>,
+) {
+    for (caster, action_state, mut ability, parts) in &mut casters {
+        if !action_state.just_pressed(&PlayerAction::Ability) || !ability.cooldown.is_finished() {
+            continue;
+        }
+        let Some((origin, velocity)) = bodies
+            .p0()
+            .get_mut(caster)
+            .ok()
+            .map(|(forces, _)| (forces.position().0, forces.linear_velocity()))
+        else {
+            continue;
+        };
+        ability.cooldown.reset();
+
+        match ability.def.effect {
+            AbilityEffect::GroundSlam {
+                speed,
+                radius,
+                knockback,
+            } => {
+                let mut bodies = bodies.p0();
+                push(&mut bodies, caster, Vec2::NEG_Y, speed);
+                for other in players.iter().filter(|&other| other != caster) {
+                    let Ok((forces, _)) = bodies.get_mut(other) else {
+                        continue;
+                    };
+                    let offset = forces.position().0 - origin;
+                    let distance = offset.length();
+                    if distance < radius {
+                        let falloff = 1. - distance / radius;
+                        push(
+                            &mut bodies,
+                            other,
+                            offset.normalize_or(Vec2::Y),
+                            knockback * falloff,
+                        );
+                    }
+                }
+            }
+            AbilityEffect::WeaponSpin { speed } => {
+                let mut bodies = bodies.p0();
+                for (weapon, _) in weapons.iter().filter(|(_, part)| part.0 == caster) {
+                    let Ok((forces, _)) = bodies.get_mut(weapon) else {
+                        continue;
+                    };
+                    let offset = forces.position().0 - origin;
+                    // Keep spinning the way the weapon is already going around the player.
+                    let spin = offset.perp_dot(forces.linear_velocity() - velocity);
+                    let direction = offset.perp().normalize_or_zero() * spin.signum();
+                    push(&mut bodies, weapon, direction, speed);
+                }
+            }
+            AbilityEffect::Teleport { distance } => {
+                let movement = action_state.axis_pair(&PlayerAction::Move);
+                let direction = if movement != Vec2::ZERO {
+                    movement.normalize()
+                } else {
+                    velocity.normalize_or(Vec2::Y)
+                };
+                let Ok(direction) = Dir2::new(direction) else {
+                    continue;
+                };
+                let radius = tuning.player.radius;
+                let inside = arena.0.bounds.inflate(-radius);
+                let distance = distance.min(distance_to_edge(inside, origin, direction));
+                // Stop at the first solid thing in the way that isn't part of the caster. The cast
+                // shape is a little smaller so that resting on the ground doesn't count.
+                let blocked = |entity: Entity| {
+                    let body = colliders
+                        .get(entity)
+                        .map_or(entity, |collider| collider.body);
+                    !sensors.contains(entity)
+                        && body != caster
+                        && !parts.iter().any(|part| part == body)
+                };
+                let distance = spatial_query
+                    .cast_shape_predicate(
+                        &Collider::circle(radius * TELEPORT_CAST_SCALE),
+                        origin,
+                        0.,
+                        direction,
+                        &ShapeCastConfig {
+                            ignore_origin_penetration: true,
+                            ..ShapeCastConfig::from_max_distance(distance)
+                        },
+                        &SpatialQueryFilter::default(),
+                        &blocked,
+                    )
+                    .map_or(distance, |hit| hit.distance);
+                let offset = direction * distance;
+                // Move every part along so that joints don't yank the player back.
+                let mut positions = bodies.p1();
+                for entity in std::iter::once(caster).chain(parts.iter()) {
+                    if let Ok(mut position) = positions.get_mut(entity) {
+                        position.0 += offset;
+                    }
+                }
+            }
+            AbilityEffect::Magnet { radius, speed } => {
+                let mut bodies = bodies.p0();
+                for (weapon, _) in weapons.iter().filter(|(_, part)| part.0 != caster) {
+                    let Ok((forces, _)) = bodies.get_mut(weapon) else {
+                        continue;
+                    };
+                    let offset = origin - forces.position().0;
+                    if offset.length() < radius {
+                        push(&mut bodies, weapon, offset.normalize_or_zero(), speed);
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Scale of the shape cast ahead of a teleport, relative to the player.
+const TELEPORT_CAST_SCALE: f32 = 0.9;
+
+/// How far `origin` can go in `direction` before leaving `rect`.
+fn distance_to_edge(rect: Rect, origin: Vec2, direction: Dir2) -> f32 {
+    let axis = |origin: f32, direction: f32, min: f32, max: f32| {
+        if direction > 0. {
+            (max - origin) / direction
+        } else if direction < 0. {
+            (min - origin) / direction
+        } else {
+            f32::INFINITY
+        }
+    };
+    axis(origin.x, direction.x, rect.min.x, rect.max.x)
+        .min(axis(origin.y, direction.y, rect.min.y, rect.max.y))
+        .max(0.)
+}
+
+/// Give a body `speed` in the given direction, regardless of its mass.
+fn push(bodies: &mut Query<(Forces, &ComputedMass)>, entity: Entity, direction: Vec2, speed: f32) {
+    if let Ok((mut forces, mass)) = bodies.get_mut(entity) {
+        forces.apply_linear_impulse(direction * speed * mass.value());
+    }
+}
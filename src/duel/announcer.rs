@@ -0,0 +1,138 @@
+//! Big text announcing the countdown before every round, eliminations and winners.
+
+use bevy::prelude::*;
+
+use crate::{
+    AppSystems,
+    duel::{
+        arena::Eliminated,
+        match_state::{MatchScore, MatchState, MatchTimer},
+        player::{MatchConfig, PlayerID},
+    },
+    screens::Screen,
+    theme::{palette::*, prelude::*},
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<LastCountdownNumber>();
+    app.add_systems(OnEnter(MatchState::Countdown), reset_countdown);
+    app.add_systems(OnEnter(MatchState::Fighting), announce_go);
+    app.add_systems(OnEnter(MatchState::RoundOver), announce_round_winner);
+    app.add_systems(OnEnter(MatchState::MatchOver), announce_match_winner);
+    app.add_systems(
+        Update,
+        (
+            announce_countdown
+                .run_if(in_state(MatchState::Countdown).and(resource_exists::<MatchTimer>)),
+            announce_eliminations.run_if(in_state(Screen::Gameplay)),
+        )
+            .in_set(AppSystems::Update),
+    );
+}
+
+const COUNTDOWN_NUMBER_SECS: f32 = 0.9;
+const ELIMINATION_SECS: f32 = 1.5;
+const ROUND_WINNER_SECS: f32 = 2.2;
+const MATCH_WINNER_SECS: f32 = 3.8;
+const FADE_SECS: f32 = 0.25;
+
+/// A banner that pops in at `top` and despawns once it has faded out.
+fn announcement(text: impl Into<String>, color: Color, top: Val, secs: f32) -> impl Bundle {
+    (
+        widget::banner(text, color),
+        Node {
+            position_type: PositionType::Absolute,
+            width: percent(100),
+            top,
+            ..default()
+        },
+        TextLayout::new_with_justify(Justify::Center),
+        FadeInOut::new(secs, FADE_SECS).with_pop(2.0).despawning(),
+        Pickable::IGNORE,
+        DespawnOnExit(Screen::Gameplay),
+    )
+}
+
+fn player_name(config: &MatchConfig, PlayerID(index): PlayerID) -> (String, Color) {
+    let color = config
+        .players
+        .get(index)
+        .map_or(HEADER_TEXT, |player| player.color);
+    (format!("P{}", index + 1), color)
+}
+
+/// The number of the current countdown announced last.
+#[derive(Resource, Debug, Default)]
+struct LastCountdownNumber(Option<u32>);
+
+/// A countdown can be cut short by an elimination, so start every countdown from scratch.
+fn reset_countdown(mut commands: Commands) {
+    commands.insert_resource(LastCountdownNumber::default());
+}
+
+fn announce_countdown(
+    mut commands: Commands,
+    timer: Res<MatchTimer>,
+    mut last_announced: ResMut<LastCountdownNumber>,
+) {
+    let number = timer.0.remaining_secs().ceil() as u32;
+    if number == 0 || last_announced.0 == Some(number) {
+        return;
+    }
+    last_announced.0 = Some(number);
+    commands.spawn(announcement(
+        number.to_string(),
+        HEADER_TEXT,
+        percent(40),
+        COUNTDOWN_NUMBER_SECS,
+    ));
+}
+
+fn announce_go(mut commands: Commands) {
+    commands.spawn(announcement(
+        "Go!",
+        HEADER_TEXT,
+        percent(40),
+        COUNTDOWN_NUMBER_SECS,
+    ));
+}
+
+fn announce_eliminations(
+    mut commands: Commands,
+    mut eliminated: MessageReader<Eliminated>,
+    config: Res<MatchConfig>,
+) {
+    for event in eliminated.read() {
+        let (name, color) = player_name(&config, event.id);
+        commands.spawn(announcement(
+            format!("{name} KO!"),
+            color,
+            percent(20),
+            ELIMINATION_SECS,
+        ));
+    }
+}
+
+fn announce_round_winner(mut commands: Commands, score: Res<MatchScore>, config: Res<MatchConfig>) {
+    let (text, color) = match score.round_winner {
+        Some(winner) => {
+            let (name, color) = player_name(&config, winner);
+            (format!("{name} wins the round!"), color)
+        }
+        None => ("Draw!".to_string(), HEADER_TEXT),
+    };
+    commands.spawn(announcement(text, color, percent(40), ROUND_WINNER_SECS));
+}
+
+fn announce_match_winner(mut commands: Commands, score: Res<MatchScore>, config: Res<MatchConfig>) {
+    let Some(winner) = score.match_winner(config.rounds_to_win) else {
+        return;
+    };
+    let (name, color) = player_name(&config, winner);
+    commands.spawn(announcement(
+        format!("{name} wins the match!"),
+        color,
+        percent(40),
+        MATCH_WINNER_SECS,
+    ));
+}
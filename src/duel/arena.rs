@@ -0,0 +1,413 @@
+//! The arena players fight in, and ring-outs for anyone flung out of it.
+//!
+//! Arenas are described by `.arena.ron` files in `assets/arenas`, listing their static
+//! geometry, spawn points, bounds and hazards.
+
+use avian2d::prelude::*;
+use bevy::{
+    asset::RenderAssetUsages,
+    prelude::*,
+    render::render_resource::{Extent3d, TextureDimension, TextureFormat},
+};
+use serde::Deserialize;
+
+use crate::{
+    asset_tracking::{LoadResource, RonAssetLoader},
+    duel::{
+        match_state::MatchState,
+        player::{Damage, MatchConfig, PLAYER_COLORS, PlayerID},
+        tuning::Tuning,
+    },
+    screens::Screen,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_asset::<Arena>();
+    app.register_asset_loader(RonAssetLoader::<Arena>::new(&["arena.ron"]));
+    app.load_resource::<ArenaAssets>();
+    app.init_resource::<CurrentArena>();
+    app.add_message::<Eliminated>();
+
+    app.add_systems(OnEnter(Screen::Gameplay), spawn_arena);
+    app.add_systems(
+        Update,
+        // Despawns are applied between the two, so nobody is eliminated twice in a frame.
+        (touch_hazards, ring_out)
+            .chain()
+            .run_if(in_state(MatchState::Countdown).or(in_state(MatchState::Fighting))),
+    );
+}
+
+/// Every arena that can be picked for a match, in the order they're listed in menus.
+const ARENA_PATHS: [&str; 3] = [
+    "arenas/classic.arena.ron",
+    "arenas/pillars.arena.ron",
+    "arenas/spike_pit.arena.ron",
+];
+
+/// An arena layout.
+#[derive(Asset, Clone, Debug, Deserialize, Reflect)]
+pub struct Arena {
+    pub name: String,
+    /// The region players have to stay inside of. Leaving it entirely is a ring-out.
+    pub bounds: Rect,
+    /// Where each player starts a round, indexed by [`PlayerID`].
+    pub spawn_points: Vec<Vec2>,
+    /// Static geometry players can stand on and bump into.
+    pub colliders: Vec<ArenaShape>,
+    pub hazards: Vec<Hazard>,
+    /// Where power-ups show up during a round.
+    #[serde(default)]
+    pub power_up_spawns: Vec<Vec2>,
+}
+
+impl Default for Arena {
+    /// An empty arena, used if the chosen one couldn't be found.
+    fn default() -> Self {
+        Self {
+            name: "Empty".to_string(),
+            bounds: Rect::from_center_size(Vec2::ZERO, Vec2::new(1200., 800.)),
+            spawn_points: Vec::new(),
+            colliders: Vec::new(),
+            hazards: Vec::new(),
+            power_up_spawns: Vec::new(),
+        }
+    }
+}
+
+/// Radius of the spawn point markers on thumbnails, in pixels.
+const THUMBNAIL_SPAWN_RADIUS: f32 = 3.;
+
+impl Arena {
+    /// A top-down picture of the arena's layout, `width` pixels wide.
+    pub fn thumbnail(&self, width: u32) -> Image {
+        let pixel = self.bounds.width() / width as f32;
+        let height = (self.bounds.height() / pixel).round().max(1.) as u32;
+
+        let mut data = Vec::with_capacity((width * height * 4) as usize);
+        for y in 0..height {
+            for x in 0..width {
+                // Image rows go down, world coordinates go up.
+                let point =
+                    self.bounds.min + Vec2::new(x as f32 + 0.5, (height - y) as f32 - 0.5) * pixel;
+                data.extend_from_slice(&self.color_at(point, pixel).to_srgba().to_u8_array());
+            }
+        }
+
+        Image::new(
+            Extent3d {
+                width,
+                height,
+                depth_or_array_layers: 1,
+            },
+            TextureDimension::D2,
+            data,
+            TextureFormat::Rgba8UnormSrgb,
+            RenderAssetUsages::default(),
+        )
+    }
+
+    /// The color of the arena at `point` on a thumbnail with the given pixel size.
+    fn color_at(&self, point: Vec2, pixel: f32) -> Color {
+        let spawn_radius = THUMBNAIL_SPAWN_RADIUS * pixel;
+        if let Some(index) = self
+            .spawn_points
+            .iter()
+            .position(|spawn| spawn.distance(point) <= spawn_radius)
+        {
+            return PLAYER_COLORS[index % PLAYER_COLORS.len()];
+        }
+        if let Some(hazard) = self
+            .hazards
+            .iter()
+            .find(|hazard| hazard.shape.contains(point, pixel))
+        {
+            // Hazards may be see-through, so draw them over the background.
+            let color = hazard.kind.color();
+            return ARENA_BACKGROUND.mix(&color.with_alpha(1.), color.alpha());
+        }
+        if self
+            .colliders
+            .iter()
+            .any(|shape| shape.contains(point, pixel))
+        {
+            return PLATFORM_COLOR;
+        }
+        ARENA_BACKGROUND
+    }
+}
+
+/// A piece of arena geometry, in world coordinates.
+#[derive(Clone, Debug, Deserialize, Reflect)]
+pub enum ArenaShape {
+    /// A convex polygon through the given vertices, in counterclockwise order.
+    Polygon(Vec<Vec2>),
+    Circle {
+        center: Vec2,
+        radius: f32,
+    },
+    /// A thin line between two points.
+    Segment(Vec2, Vec2),
+}
+
+/// How thick segments are drawn. They have no thickness as far as physics is concerned.
+const SEGMENT_WIDTH: f32 = 4.;
+
+impl ArenaShape {
+    /// The collider and mesh of this shape, and where to put them.
+    ///
+    /// Returns `None` for polygons that aren't convex.
+    fn build(&self) -> Option<(Collider, Mesh, Transform)> {
+        match self {
+            Self::Polygon(vertices) => {
+                let polygon = ConvexPolygon::new(vertices.iter().copied()).ok()?;
+                let collider = Collider::convex_hull(vertices.clone())?;
+                Some((collider, polygon.into(), Transform::default()))
+            }
+            &Self::Circle { center, radius } => Some((
+                Collider::circle(radius),
+                Circle::new(radius).into(),
+                Transform::from_translation(center.extend(0.)),
+            )),
+            &Self::Segment(start, end) => {
+                let length = start.distance(end);
+                let half = Vec2::X * length / 2.;
+                Some((
+                    Collider::segment(-half, half),
+                    Rectangle::new(length, SEGMENT_WIDTH).into(),
+                    Transform::from_translation(start.midpoint(end).extend(0.))
+                        .with_rotation(Quat::from_rotation_z((end - start).to_angle())),
+                ))
+            }
+        }
+    }
+
+    /// Whether `point` lies inside the shape, drawing segments at least `min_width` wide.
+    fn contains(&self, point: Vec2, min_width: f32) -> bool {
+        match self {
+            // Inside a counterclockwise convex polygon means left of every edge.
+            Self::Polygon(vertices) => vertices
+                .iter()
+                .zip(vertices.iter().cycle().skip(1))
+                .all(|(&a, &b)| (b - a).perp_dot(point - a) >= 0.),
+            &Self::Circle { center, radius } => point.distance(center) <= radius,
+            &Self::Segment(start, end) => {
+                let along = end - start;
+                let t = ((point - start).dot(along) / along.length_squared()).clamp(0., 1.);
+                point.distance(start + along * t) <= SEGMENT_WIDTH.max(min_width) / 2.
+            }
+        }
+    }
+}
+
+/// Part of an arena that's dangerous to touch.
+#[derive(Clone, Debug, Deserialize, Reflect)]
+pub struct Hazard {
+    pub shape: ArenaShape,
+    pub kind: HazardKind,
+}
+
+#[derive(Component, Clone, Copy, Debug, Deserialize, Reflect)]
+pub enum HazardKind {
+    /// Knocks out anyone touching it on the spot.
+    KillZone,
+    /// Hurts anyone touching it and bounces them back the way they came.
+    Spikes {
+        /// Damage percent dealt per touch.
+        damage: f32,
+        /// Knockback speed, before damage scaling.
+        knockback: f32,
+    },
+}
+
+impl HazardKind {
+    fn color(self) -> Color {
+        match self {
+            Self::KillZone => KILL_ZONE_COLOR,
+            Self::Spikes { .. } => SPIKES_COLOR,
+        }
+    }
+}
+
+/// Handles to every arena in [`ARENA_PATHS`].
+#[derive(Resource, Asset, Clone, Reflect)]
+#[reflect(Resource)]
+pub struct ArenaAssets {
+    #[dependency]
+    pub arenas: Vec<Handle<Arena>>,
+}
+
+impl FromWorld for ArenaAssets {
+    fn from_world(world: &mut World) -> Self {
+        let assets = world.resource::<AssetServer>();
+        Self {
+            arenas: ARENA_PATHS.iter().map(|&path| assets.load(path)).collect(),
+        }
+    }
+}
+
+/// The arena of the match in progress.
+#[derive(Resource, Debug, Clone, Default)]
+pub struct CurrentArena(pub Arena);
+
+/// Marks the static platforms and hazards of the current arena.
+#[derive(Component, Debug, Default)]
+pub struct ArenaGeometry;
+
+/// Written when a player is knocked out of the match.
+#[derive(Message, Debug, Clone, Copy)]
+pub struct Eliminated {
+    pub player: Entity,
+    pub id: PlayerID,
+}
+
+const ARENA_BACKGROUND: Color = Color::srgb(0.2, 0.2, 0.22);
+const PLATFORM_COLOR: Color = Color::srgb(0.5, 0.5, 0.55);
+const KILL_ZONE_COLOR: Color = Color::srgba(0.9, 0.2, 0.2, 0.4);
+const SPIKES_COLOR: Color = Color::srgb(0.75, 0.3, 0.3);
+
+fn spawn_arena(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    config: Res<MatchConfig>,
+    arena_assets: Res<ArenaAssets>,
+    arenas: Res<Assets<Arena>>,
+    tuning: Res<Tuning>,
+) {
+    let arena = arena_assets
+        .arenas
+        .get(config.arena)
+        .and_then(|handle| arenas.get(handle))
+        .cloned()
+        .unwrap_or_else(|| {
+            warn!("Arena {} doesn't exist, using an empty one", config.arena);
+            Arena::default()
+        });
+
+    commands.spawn((
+        Name::new("Arena Bounds"),
+        Mesh2d(meshes.add(Rectangle::from_size(arena.bounds.size()))),
+        MeshMaterial2d(materials.add(ARENA_BACKGROUND)),
+        Transform::from_translation(arena.bounds.center().extend(-1.)),
+        DespawnOnExit(Screen::Gameplay),
+    ));
+
+    let platform_material = materials.add(PLATFORM_COLOR);
+    for shape in &arena.colliders {
+        let Some((collider, mesh, transform)) = shape.build() else {
+            warn!("Skipping concave platform in arena {:?}", arena.name);
+            continue;
+        };
+        commands.spawn((
+            Name::new("Platform"),
+            ArenaGeometry,
+            RigidBody::Static,
+            collider,
+            Friction::new(tuning.friction),
+            Mesh2d(meshes.add(mesh)),
+            MeshMaterial2d(platform_material.clone()),
+            transform,
+            DespawnOnExit(Screen::Gameplay),
+        ));
+    }
+
+    for hazard in &arena.hazards {
+        let Some((collider, mesh, transform)) = hazard.shape.build() else {
+            warn!("Skipping concave hazard in arena {:?}", arena.name);
+            continue;
+        };
+        let mut entity = commands.spawn((
+            Name::new("Hazard"),
+            ArenaGeometry,
+            hazard.kind,
+            RigidBody::Static,
+            collider,
+            CollisionEventsEnabled,
+            Mesh2d(meshes.add(mesh)),
+            MeshMaterial2d(materials.add(hazard.kind.color())),
+            // Draw hazards over the platforms they're on.
+            transform.with_translation(transform.translation.with_z(0.1)),
+            DespawnOnExit(Screen::Gameplay),
+        ));
+        // Kill zones can be fallen into, spikes are solid.
+        if let HazardKind::KillZone = hazard.kind {
+            entity.insert(Sensor);
+        }
+    }
+
+    commands.insert_resource(CurrentArena(arena));
+}
+
+/// Eliminate players whose collider has left the arena bounds completely.
+fn ring_out(
+    mut commands: Commands,
+    arena: Res<CurrentArena>,
+    players: Query<(Entity, &PlayerID, &ColliderAabb)>,
+    mut eliminated: MessageWriter<Eliminated>,
+) {
+    for (entity, &id, aabb) in &players {
+        let player_rect = Rect::from_corners(aabb.min, aabb.max);
+        if !arena.0.bounds.intersect(player_rect).is_empty() {
+            continue;
+        }
+        eliminated.write(Eliminated { player: entity, id });
+        // Weapons and joints are player parts, so they go along with the player.
+        commands.entity(entity).try_despawn();
+    }
+}
+
+fn touch_hazards(
+    mut commands: Commands,
+    mut collision_starts: MessageReader<CollisionStart>,
+    collisions: Collisions,
+    hazards: Query<(&HazardKind, &Position)>,
+    mut players: Query<(&PlayerID, &Position, &mut LinearVelocity, &mut Damage)>,
+    mut eliminated: MessageWriter<Eliminated>,
+    mut knocked_out: Local<Vec<Entity>>,
+) {
+    knocked_out.clear();
+    for event in collision_starts.read() {
+        for (hazard, player) in [
+            (event.collider1, event.collider2),
+            (event.collider2, event.collider1),
+        ] {
+            let (Ok((&kind, hazard_position)), Ok((&id, position, mut velocity, mut damage))) =
+                (hazards.get(hazard), players.get_mut(player))
+            else {
+                continue;
+            };
+            match kind {
+                HazardKind::KillZone => {
+                    // Touching several kill zones at once still only counts once.
+                    if knocked_out.contains(&player) {
+                        continue;
+                    }
+                    knocked_out.push(player);
+                    eliminated.write(Eliminated { player, id });
+                    commands.entity(player).try_despawn();
+                }
+                HazardKind::Spikes {
+                    damage: hurt,
+                    knockback,
+                } => {
+                    damage.0 += hurt;
+                    // The solver has already changed the player's velocity, so push them away
+                    // along the contact normal instead.
+                    let direction = collisions
+                        .get(hazard, player)
+                        .and_then(|contacts| {
+                            let normal = contacts.manifolds.first()?.normal;
+                            Some(if contacts.collider1 == hazard {
+                                normal
+                            } else {
+                                -normal
+                            })
+                        })
+                        .unwrap_or_else(|| (position.0 - hazard_position.0).normalize_or(Vec2::Y));
+                    velocity.0 = direction * knockback * (1. + damage.0 / 100.);
+                }
+            }
+        }
+    }
+}
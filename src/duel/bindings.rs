@@ -0,0 +1,336 @@
+//! User-editable key and gamepad bindings: one profile per half of the keyboard, and a gamepad
+//! profile for every player slot.
+//!
+//! Bindings are saved along with the rest of the [`Settings`](crate::settings::Settings).
+
+use std::{collections::BTreeMap, fmt};
+
+use bevy::prelude::*;
+use leafwing_input_manager::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::duel::{
+    control::{InputDevice, PlayerAction},
+    player::{MAX_PLAYERS, PlayerID},
+};
+
+/// Something a player can rebind.
+#[derive(
+    Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize, Reflect,
+)]
+pub enum Control {
+    MoveUp,
+    MoveDown,
+    MoveLeft,
+    MoveRight,
+    Dash,
+    Guard,
+    Fire,
+    Ability,
+}
+
+impl Control {
+    pub const ALL: [Self; 8] = [
+        Self::MoveUp,
+        Self::MoveDown,
+        Self::MoveLeft,
+        Self::MoveRight,
+        Self::Dash,
+        Self::Guard,
+        Self::Fire,
+        Self::Ability,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::MoveUp => "Move Up",
+            Self::MoveDown => "Move Down",
+            Self::MoveLeft => "Move Left",
+            Self::MoveRight => "Move Right",
+            Self::Dash => "Dash",
+            Self::Guard => "Guard",
+            Self::Fire => "Fire",
+            Self::Ability => "Ability",
+        }
+    }
+}
+
+/// Keys to leave a lobby seat with, for the left and right half of the keyboard.
+pub const KEYBOARD_LEFT_BACK: KeyCode = KeyCode::KeyQ;
+pub const KEYBOARD_RIGHT_BACK: KeyCode = KeyCode::Backspace;
+/// The button to leave a lobby seat with on a gamepad.
+pub const GAMEPAD_BACK: GamepadButton = GamepadButton::East;
+
+/// Keys that pause the game, close menus or leave lobby seats, so they can't be bound to a
+/// [`Control`].
+pub const RESERVED_KEYS: [KeyCode; 4] = [
+    KeyCode::KeyP,
+    KeyCode::Escape,
+    KEYBOARD_LEFT_BACK,
+    KEYBOARD_RIGHT_BACK,
+];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, Reflect)]
+pub enum Binding {
+    Key(KeyCode),
+    Button(GamepadButton),
+}
+
+impl Binding {
+    pub fn is_reserved(self) -> bool {
+        match self {
+            Self::Key(key) => RESERVED_KEYS.contains(&key),
+            Self::Button(button) => button == GAMEPAD_BACK,
+        }
+    }
+
+    fn boxed(self) -> Box<dyn Buttonlike> {
+        match self {
+            Self::Key(key) => Box::new(key),
+            Self::Button(button) => Box::new(button),
+        }
+    }
+}
+
+impl fmt::Display for Binding {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Key(key) => {
+                let name = format!("{key:?}");
+                let name = name
+                    .strip_prefix("Key")
+                    .or_else(|| name.strip_prefix("Digit"))
+                    .unwrap_or(&name);
+                f.write_str(name)
+            }
+            Self::Button(button) => write!(f, "{button:?}"),
+        }
+    }
+}
+
+/// Which set of bindings a player uses. Gamepad profiles belong to a player slot rather than a
+/// particular gamepad, so they apply to whichever gamepad that player joins with.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Reflect)]
+pub enum ControlProfile {
+    KeyboardLeft,
+    KeyboardRight,
+    /// The gamepad profile of the player with this [`PlayerID`] index.
+    Gamepad(usize),
+}
+
+impl ControlProfile {
+    pub const ALL: [Self; 2 + MAX_PLAYERS] = [
+        Self::KeyboardLeft,
+        Self::KeyboardRight,
+        Self::Gamepad(0),
+        Self::Gamepad(1),
+        Self::Gamepad(2),
+        Self::Gamepad(3),
+    ];
+
+    pub fn for_player(device: InputDevice, PlayerID(index): PlayerID) -> Option<Self> {
+        match device {
+            InputDevice::KeyboardLeft => Some(Self::KeyboardLeft),
+            InputDevice::KeyboardRight => Some(Self::KeyboardRight),
+            InputDevice::Gamepad(_) => Some(Self::Gamepad(index)),
+            InputDevice::Pointer => None,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        const GAMEPAD_LABELS: [&str; MAX_PLAYERS] = [
+            "Gamepad (Player 1)",
+            "Gamepad (Player 2)",
+            "Gamepad (Player 3)",
+            "Gamepad (Player 4)",
+        ];
+        match self {
+            Self::KeyboardLeft => "Keyboard (Left)",
+            Self::KeyboardRight => "Keyboard (Right)",
+            Self::Gamepad(index) => GAMEPAD_LABELS[index],
+        }
+    }
+
+    pub fn uses_keyboard(self) -> bool {
+        !matches!(self, Self::Gamepad(_))
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, Reflect)]
+#[serde(transparent)]
+pub struct Profile(BTreeMap<Control, Binding>);
+
+impl Profile {
+    pub fn get(&self, control: Control) -> Option<Binding> {
+        self.0.get(&control).copied()
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, Reflect)]
+#[serde(default)]
+pub struct Bindings {
+    keyboard_left: Profile,
+    keyboard_right: Profile,
+    /// Indexed by [`PlayerID`].
+    gamepads: [Profile; MAX_PLAYERS],
+}
+
+impl Default for Bindings {
+    fn default() -> Self {
+        use Control::*;
+
+        let keys = |keys: [KeyCode; 8]| {
+            Profile(
+                Control::ALL
+                    .into_iter()
+                    .zip(keys.map(Binding::Key))
+                    .collect(),
+            )
+        };
+        Self {
+            keyboard_left: keys([
+                KeyCode::KeyW,
+                KeyCode::KeyS,
+                KeyCode::KeyA,
+                KeyCode::KeyD,
+                KeyCode::ShiftLeft,
+                KeyCode::Space,
+                KeyCode::KeyE,
+                KeyCode::KeyF,
+            ]),
+            keyboard_right: keys([
+                KeyCode::ArrowUp,
+                KeyCode::ArrowDown,
+                KeyCode::ArrowLeft,
+                KeyCode::ArrowRight,
+                KeyCode::ShiftRight,
+                KeyCode::Enter,
+                KeyCode::ControlRight,
+                KeyCode::AltRight,
+            ]),
+            gamepads: std::array::from_fn(|_| {
+                Profile(
+                    [
+                        (MoveUp, GamepadButton::DPadUp),
+                        (MoveDown, GamepadButton::DPadDown),
+                        (MoveLeft, GamepadButton::DPadLeft),
+                        (MoveRight, GamepadButton::DPadRight),
+                        (Dash, GamepadButton::South),
+                        (Guard, GamepadButton::RightTrigger),
+                        (Fire, GamepadButton::West),
+                        (Ability, GamepadButton::North),
+                    ]
+                    .into_iter()
+                    .map(|(control, button)| (control, Binding::Button(button)))
+                    .collect(),
+                )
+            }),
+        }
+    }
+}
+
+impl Bindings {
+    pub fn profile(&self, profile: ControlProfile) -> &Profile {
+        match profile {
+            ControlProfile::KeyboardLeft => &self.keyboard_left,
+            ControlProfile::KeyboardRight => &self.keyboard_right,
+            ControlProfile::Gamepad(index) => &self.gamepads[index],
+        }
+    }
+
+    fn profile_mut(&mut self, profile: ControlProfile) -> &mut Profile {
+        match profile {
+            ControlProfile::KeyboardLeft => &mut self.keyboard_left,
+            ControlProfile::KeyboardRight => &mut self.keyboard_right,
+            ControlProfile::Gamepad(index) => &mut self.gamepads[index],
+        }
+    }
+
+    /// Bind `control` to `binding`.
+    ///
+    /// Both keyboard profiles share a keyboard, so a key can only be bound once across them. If
+    /// `binding` was already in use, the control it was bound to gets the old binding of
+    /// `control` instead, and is returned.
+    pub fn rebind(
+        &mut self,
+        profile: ControlProfile,
+        control: Control,
+        binding: Binding,
+    ) -> Option<(ControlProfile, Control)> {
+        let old = self.profile(profile).get(control);
+        let conflict = ControlProfile::ALL
+            .into_iter()
+            .filter(|&other| other == profile || other.uses_keyboard() && profile.uses_keyboard())
+            .flat_map(|other| {
+                self.profile(other)
+                    .0
+                    .iter()
+                    .filter(|&(_, &bound)| bound == binding)
+                    .map(move |(&control, _)| (other, control))
+            })
+            .find(|&conflict| conflict != (profile, control));
+
+        if let Some((other, other_control)) = conflict {
+            let other = self.profile_mut(other);
+            match old {
+                Some(old) => other.0.insert(other_control, old),
+                None => other.0.remove(&other_control),
+            };
+        }
+        self.profile_mut(profile).0.insert(control, binding);
+        conflict
+    }
+
+    /// The input map for the player `id`, using the given device.
+    pub fn input_map(&self, device: InputDevice, id: PlayerID) -> InputMap<PlayerAction> {
+        let mut input_map = InputMap::default();
+        if let InputDevice::Gamepad(gamepad) = device {
+            input_map.insert_dual_axis(PlayerAction::Move, GamepadStick::LEFT);
+            input_map.insert_dual_axis(PlayerAction::Aim, GamepadStick::RIGHT);
+            input_map.set_gamepad(gamepad);
+        }
+        // Actions are filled in from the pointer and on-screen buttons instead.
+        let Some(profile) = ControlProfile::for_player(device, id) else {
+            return input_map;
+        };
+        let profile = self.profile(profile);
+
+        if let [Some(up), Some(down), Some(left), Some(right)] = [
+            Control::MoveUp,
+            Control::MoveDown,
+            Control::MoveLeft,
+            Control::MoveRight,
+        ]
+        .map(|control| {
+            profile
+                .get(control)
+                .filter(|binding| !binding.is_reserved())
+        }) {
+            input_map.insert_dual_axis(
+                PlayerAction::Move,
+                VirtualDPad {
+                    up: up.boxed(),
+                    down: down.boxed(),
+                    left: left.boxed(),
+                    right: right.boxed(),
+                    processors: Vec::new(),
+                },
+            );
+        }
+        for (control, action) in [
+            (Control::Dash, PlayerAction::Dash),
+            (Control::Guard, PlayerAction::Gaurd),
+            (Control::Fire, PlayerAction::Fire),
+            (Control::Ability, PlayerAction::Ability),
+        ] {
+            // Settings saved before a key was reserved may still use it.
+            if let Some(binding) = profile
+                .get(control)
+                .filter(|binding| !binding.is_reserved())
+            {
+                input_map.insert_boxed(action, binding.boxed());
+            }
+        }
+        input_map
+    }
+}
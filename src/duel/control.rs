@@ -1,148 +1,214 @@
-use crate::screens::Screen;
+use crate::{AppSystems, screens::Screen, settings::Settings};
 
-use super::player::{PlayerID, Speed};
+use super::{
+    guard::{GUARD_SPEED_MULTIPLIER, Guard, Stunned},
+    match_state::MatchState,
+    player::{MatchConfig, PlayerID, PlayerPart, Speed},
+    tuning::Tuning,
+    weapon::WeaponBody,
+};
 use avian2d::prelude::*;
-use bevy::{input::gamepad::GamepadConnectionEvent, prelude::*};
+use bevy::{
+    input::gamepad::{GamepadConnection, GamepadConnectionEvent},
+    prelude::*,
+    window::PrimaryWindow,
+};
 use leafwing_input_manager::prelude::*;
 
 pub(super) fn plugin(app: &mut App) {
     app.add_plugins(InputManagerPlugin::<PlayerAction>::default())
-        .add_systems(Update, handle_inputs)
-        .add_systems(OnEnter(Screen::Gameplay), setup_gamepads)
         .add_systems(
             Update,
-            handle_gamepad_connection
-                .run_if(in_state(Screen::Gameplay).and(on_message::<GamepadConnectionEvent>)),
-        );
+            (
+                (
+                    reassign_gamepads.run_if(on_message::<GamepadConnectionEvent>),
+                    apply_bindings
+                        .after(reassign_gamepads)
+                        .run_if(resource_changed::<Settings>),
+                    aim_with_mouse,
+                )
+                    .in_set(AppSystems::RecordInput),
+                (handle_inputs, aim_weapons).in_set(AppSystems::Update),
+            )
+                .run_if(in_state(Screen::Gameplay)),
+        )
+        .add_systems(
+            Update,
+            ignore_inputs
+                .in_set(AppSystems::RecordInput)
+                .run_if(in_state(MatchState::Countdown)),
+        )
+        .add_systems(OnExit(MatchState::Countdown), restore_inputs);
 }
 
-#[derive(Component)]
-#[relationship(relationship_target = AssignedPlayer)]
-struct AssignedGamepad(Entity);
+/// What a player controls their character with.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum InputDevice {
+    /// The left half of the keyboard, moving with WASD.
+    KeyboardLeft,
+    /// The right half of the keyboard, moving with the arrow keys.
+    KeyboardRight,
+    Gamepad(Entity),
+    /// The mouse or a touch screen, see [`pointer`](super::pointer).
+    Pointer,
+}
 
-#[derive(Component)]
-#[relationship_target(relationship = AssignedGamepad)]
-struct AssignedPlayer(Entity);
+/// Marks a player that aims with the mouse while holding the right mouse button.
+///
+/// Only the left half of the keyboard and pointer players get the mouse, as the right hand is
+/// needed for it.
+#[derive(Component, Debug)]
+pub struct MouseAim;
 
 #[derive(Actionlike, Clone, Copy, PartialEq, Eq, Hash, Debug, Reflect)]
 pub enum PlayerAction {
     #[actionlike(DualAxis)]
     Move,
+    /// Where to swing the weapon, relative to the player.
+    #[actionlike(DualAxis)]
+    Aim,
     Dash,
     Gaurd,
+    Fire,
+    Ability,
 }
 
-pub fn default_input_map() -> InputMap<PlayerAction> {
-    let mut input_map = InputMap::default();
-
-    // Gamepad
-    input_map.insert_dual_axis(PlayerAction::Move, GamepadStick::LEFT);
-    input_map.insert(PlayerAction::Dash, GamepadButton::South);
-    input_map.insert(PlayerAction::Gaurd, GamepadButton::RightTrigger);
-
-    // Keyboard
-    input_map.insert_dual_axis(PlayerAction::Move, VirtualDPad::wasd());
-    input_map.insert(PlayerAction::Dash, KeyCode::ShiftLeft);
-    input_map.insert(PlayerAction::Gaurd, KeyCode::Space);
-
-    input_map
+/// Whether a gamepad in use by a player was disconnected this frame.
+pub fn player_gamepad_disconnected(
+    mut connections: MessageReader<GamepadConnectionEvent>,
+    config: Res<MatchConfig>,
+) -> bool {
+    connections.read().any(|event| {
+        event.disconnected()
+            && config
+                .players
+                .iter()
+                .any(|player| player.device == InputDevice::Gamepad(event.gamepad))
+    })
 }
 
-fn handle_inputs(mut query: Query<(&ActionState<PlayerAction>, Forces, &Speed), With<PlayerID>>) {
-    for (action_state, mut forces, Speed(speed)) in query.iter_mut() {
-        let movement = action_state.axis_pair(&PlayerAction::Move);
-        let velocity = forces.linear_velocity();
-
-        // if we are changing direction, apply an impulse to quickly change direction
-        // if movement
-        //     .normalize_or_zero()
-        //     .dot(velocity.normalize_or_zero())
-        //     < -0.7
-        // {
-        //     let impulse = -velocity.normalize_or_zero() * (*speed * 0.1);
-        //     forces.apply_linear_impulse(impulse);
-        // }
-        forces.apply_force(movement * *speed);
+/// Reconnecting a gamepad keeps its entity, so its player gets it back on their own. Give a
+/// newly connected gamepad to a player whose gamepad is gone instead.
+fn reassign_gamepads(
+    mut connections: MessageReader<GamepadConnectionEvent>,
+    gamepads: Query<(), With<Gamepad>>,
+    mut config: ResMut<MatchConfig>,
+    mut players: Query<(&PlayerID, &mut InputMap<PlayerAction>)>,
+) {
+    for event in connections.read() {
+        let GamepadConnection::Connected { .. } = event.connection else {
+            continue;
+        };
+        let device = InputDevice::Gamepad(event.gamepad);
+        if config.players.iter().any(|player| player.device == device) {
+            continue;
+        }
+        let Some(index) = config.players.iter().position(|player| {
+            matches!(player.device, InputDevice::Gamepad(gamepad) if !gamepads.contains(gamepad))
+        }) else {
+            continue;
+        };
+        info!("Assigned gamepad {} to player {}", event.gamepad, index + 1);
+        config.players[index].device = device;
+        for (_, mut input_map) in players.iter_mut().filter(|(id, _)| id.0 == index) {
+            input_map.set_gamepad(event.gamepad);
+        }
+    }
+}
 
-        if action_state.just_pressed(&PlayerAction::Dash) {
-            forces.apply_linear_impulse(movement * (*speed * 5.0));
-            println!("Player is dashing!");
+/// Controls can be rebound from the pause menu, so rebuild the input maps of players already
+/// in the arena.
+fn apply_bindings(
+    settings: Res<Settings>,
+    config: Res<MatchConfig>,
+    mut players: Query<(&PlayerID, &mut InputMap<PlayerAction>)>,
+) {
+    for (&id, mut input_map) in &mut players {
+        if let Some(player) = config.players.get(id.0) {
+            *input_map = settings.bindings.input_map(player.device, id);
         }
+    }
+}
 
-        if action_state.just_pressed(&PlayerAction::Gaurd) {
-            println!("Player is guarding!");
+/// Players can't act until the countdown before a round is over.
+fn ignore_inputs(mut players: Query<&mut ActionState<PlayerAction>>) {
+    for mut action_state in &mut players {
+        if !action_state.disabled() {
+            action_state.disable();
         }
     }
 }
 
-fn setup_gamepads(
-    mut commands: Commands,
-    mut gamepads: Query<Entity, With<Gamepad>>,
-    mut without_gamepad: Query<(Entity, &mut InputMap<PlayerAction>), Without<AssignedGamepad>>,
+fn restore_inputs(mut players: Query<&mut ActionState<PlayerAction>>) {
+    for mut action_state in &mut players {
+        action_state.enable();
+    }
+}
+
+fn handle_inputs(
+    mut query: Query<
+        (&ActionState<PlayerAction>, Forces, &Speed, &Guard),
+        (With<PlayerID>, Without<Stunned>),
+    >,
 ) {
-    // Assign gamepads to players without gamepads
-    for (entity, mut input_map) in without_gamepad.iter_mut() {
-        if let Some(gamepad_entity) = gamepads.iter_mut().next() {
-            commands
-                .entity(entity)
-                .insert(AssignedGamepad(gamepad_entity));
-            input_map.set_gamepad(gamepad_entity);
-            println!(
-                "Assigned gamepad {:?} to player entity {:?} on setup",
-                gamepad_entity, entity
-            );
-        }
+    for (action_state, mut forces, Speed(speed), guard) in query.iter_mut() {
+        let movement = action_state.axis_pair(&PlayerAction::Move);
+        let speed_multiplier = if guard.active {
+            GUARD_SPEED_MULTIPLIER
+        } else {
+            1.
+        };
+        forces.apply_force(movement * *speed * speed_multiplier);
     }
 }
 
-fn handle_gamepad_connection(
-    mut commands: Commands,
-    mut ev: MessageReader<GamepadConnectionEvent>,
-    mut q: Query<(
-        Entity,
-        &mut InputMap<PlayerAction>,
-        Option<&AssignedGamepad>,
-    )>,
+/// How far the cursor has to be from the player to aim at full strength.
+const MOUSE_AIM_RANGE: f32 = 150.;
+
+/// Mouse position isn't something the input map can bind, so fill in [`PlayerAction::Aim`]
+/// by hand for players using the mouse.
+fn aim_with_mouse(
+    mouse: Res<ButtonInput<MouseButton>>,
+    window: Single<&Window, With<PrimaryWindow>>,
+    camera: Single<(&Camera, &GlobalTransform)>,
+    mut players: Query<(&mut ActionState<PlayerAction>, &GlobalTransform), With<MouseAim>>,
 ) {
-    use bevy::input::gamepad::GamepadConnection;
-    for event in ev.read() {
-        match &event.connection {
-            GamepadConnection::Connected {
-                name,
-                vendor_id,
-                product_id,
-            } => {
-                println!(
-                    "Gamepad connected: {:?}, name: {}, vendor_id: {:?}, product_id: {:?}",
-                    event.gamepad, name, vendor_id, product_id
-                );
-                let Some((entity, mut input_map, gamepad)) = q.iter_mut().next() else {
-                    println!("No available player entities to assign the gamepad to.");
-                    continue;
-                };
-                if gamepad.is_some() {
-                    continue;
-                }
-                commands
-                    .entity(entity)
-                    .insert(AssignedGamepad(event.gamepad));
-                input_map.set_gamepad(event.gamepad);
-                println!(
-                    "Assigned gamepad {:?} to player entity {:?}",
-                    event.gamepad, entity
-                );
-            }
-            GamepadConnection::Disconnected => {
-                let Some((entity, mut input_map, _)) = q
-                    .iter_mut()
-                    .find(|(_, input_map, _)| input_map.gamepad() == Some(event.gamepad))
-                else {
-                    continue;
-                };
-                commands.entity(entity).remove::<AssignedGamepad>();
-                input_map.clear_gamepad();
-                println!("Removed gamepad assignment from player entity {:?}", entity);
+    let cursor = cursor_position(&window, &camera);
+    for (mut action_state, transform) in &mut players {
+        let aim = match cursor {
+            Some(cursor) if mouse.pressed(MouseButton::Right) => {
+                ((cursor - transform.translation().truncate()) / MOUSE_AIM_RANGE)
+                    .clamp_length_max(1.)
             }
-        }
+            _ => Vec2::ZERO,
+        };
+        action_state.set_axis_pair(&PlayerAction::Aim, aim);
+    }
+}
+
+/// Where the mouse cursor is in the world, if it's over the window.
+pub(super) fn cursor_position(
+    window: &Window,
+    (camera, camera_transform): &(&Camera, &GlobalTransform),
+) -> Option<Vec2> {
+    window
+        .cursor_position()
+        .and_then(|cursor| camera.viewport_to_world_2d(camera_transform, cursor).ok())
+}
+
+/// Pull weapons towards where their player is aiming, so that they swing around to that side.
+fn aim_weapons(
+    tuning: Res<Tuning>,
+    players: Query<&ActionState<PlayerAction>, (With<PlayerID>, Without<Stunned>)>,
+    mut weapons: Query<(Forces, &ComputedMass, &PlayerPart), With<WeaponBody>>,
+) {
+    for (mut forces, mass, part) in &mut weapons {
+        let Ok(action_state) = players.get(part.0) else {
+            continue;
+        };
+        let aim = action_state
+            .axis_pair(&PlayerAction::Aim)
+            .clamp_length_max(1.);
+        forces.apply_force(aim * tuning.weapon.aim_acceleration * mass.value());
     }
 }
@@ -0,0 +1,168 @@
+//! Dashing: a quick burst of speed with a short window of invulnerability.
+
+use avian2d::prelude::*;
+use bevy::prelude::*;
+use leafwing_input_manager::prelude::*;
+
+use crate::{
+    AppSystems, PausableSystems,
+    duel::{
+        control::PlayerAction,
+        guard::Stunned,
+        player::{PlayerID, Speed},
+    },
+    screens::Screen,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_message::<DashStarted>();
+    app.add_systems(
+        Update,
+        (
+            (tick_dash, tick_dash_trails)
+                .in_set(AppSystems::TickTimers)
+                .in_set(PausableSystems),
+            dash.in_set(AppSystems::RecordInput).in_set(PausableSystems),
+            spawn_dash_trails
+                .in_set(AppSystems::Update)
+                .in_set(PausableSystems),
+        )
+            .run_if(in_state(Screen::Gameplay)),
+    );
+}
+
+/// Dash impulse relative to the player's [`Speed`].
+const DASH_IMPULSE_PER_SPEED: f32 = 5.;
+const DASH_TRAIL_SECS: f32 = 0.25;
+const DASH_TRAIL_RADIUS: f32 = 15.;
+const DASH_TRAIL_COLOR: Color = Color::srgba(1., 1., 1., 0.4);
+
+#[derive(Component, Debug, Clone)]
+pub struct Dash {
+    /// Dashes available right now.
+    pub charges: u32,
+    pub max_charges: u32,
+    /// Time to regain a single charge. Only ticks while not at full charges.
+    pub cooldown: Timer,
+    /// Hits are ignored until this runs out.
+    pub intangible: Timer,
+}
+
+impl Default for Dash {
+    fn default() -> Self {
+        let mut intangible = Timer::from_seconds(0.2, TimerMode::Once);
+        intangible.finish();
+        Self {
+            charges: 2,
+            max_charges: 2,
+            cooldown: Timer::from_seconds(1.2, TimerMode::Repeating),
+            intangible,
+        }
+    }
+}
+
+impl Dash {
+    /// Whether the player is in the invulnerable part of a dash.
+    pub fn is_intangible(&self) -> bool {
+        !self.intangible.is_finished()
+    }
+}
+
+/// Written when a player starts dashing.
+#[derive(Message, Debug, Clone, Copy)]
+pub struct DashStarted {
+    pub player: Entity,
+    pub direction: Vec2,
+}
+
+fn tick_dash(time: Res<Time>, mut dashes: Query<&mut Dash>) {
+    for mut dash in &mut dashes {
+        dash.intangible.tick(time.delta());
+        if dash.charges < dash.max_charges {
+            dash.cooldown.tick(time.delta());
+            if dash.cooldown.just_finished() {
+                dash.charges += 1;
+            }
+        }
+    }
+}
+
+fn dash(
+    mut players: Query<
+        (
+            Entity,
+            &ActionState<PlayerAction>,
+            &mut Dash,
+            &Speed,
+            Forces,
+        ),
+        (With<PlayerID>, Without<Stunned>),
+    >,
+    mut dash_started: MessageWriter<DashStarted>,
+) {
+    for (entity, action_state, mut dash, Speed(speed), mut forces) in &mut players {
+        if !action_state.just_pressed(&PlayerAction::Dash) || dash.charges == 0 {
+            continue;
+        }
+
+        // Without any input, keep going the way we're already going.
+        let movement = action_state.axis_pair(&PlayerAction::Move);
+        let direction = if movement != Vec2::ZERO {
+            movement.normalize()
+        } else {
+            forces.linear_velocity().normalize_or_zero()
+        };
+        if direction == Vec2::ZERO {
+            continue;
+        }
+
+        dash.charges -= 1;
+        dash.intangible.reset();
+        forces.apply_linear_impulse(direction * (*speed * DASH_IMPULSE_PER_SPEED));
+        dash_started.write(DashStarted {
+            player: entity,
+            direction,
+        });
+    }
+}
+
+#[derive(Component, Debug, Clone)]
+struct DashTrail(Timer);
+
+fn spawn_dash_trails(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    mut dash_started: MessageReader<DashStarted>,
+    players: Query<&Transform>,
+) {
+    for event in dash_started.read() {
+        let Ok(transform) = players.get(event.player) else {
+            continue;
+        };
+        let position = transform.translation.truncate() - event.direction * DASH_TRAIL_RADIUS;
+        commands.spawn((
+            Name::new("Dash Trail"),
+            DashTrail(Timer::from_seconds(DASH_TRAIL_SECS, TimerMode::Once)),
+            Mesh2d(meshes.add(Circle::new(DASH_TRAIL_RADIUS))),
+            MeshMaterial2d(materials.add(DASH_TRAIL_COLOR)),
+            Transform::from_translation(position.extend(-0.5)),
+            DespawnOnExit(Screen::Gameplay),
+        ));
+    }
+}
+
+fn tick_dash_trails(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut trails: Query<(Entity, &mut DashTrail, &mut Transform)>,
+) {
+    for (entity, mut trail, mut transform) in &mut trails {
+        trail.0.tick(time.delta());
+        if trail.0.is_finished() {
+            commands.entity(entity).despawn();
+        } else {
+            transform.scale = Vec3::splat(trail.0.fraction_remaining());
+        }
+    }
+}
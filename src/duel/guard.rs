@@ -0,0 +1,136 @@
+//! Guarding against weapon hits.
+//!
+//! Holding [`PlayerAction::Gaurd`] softens incoming hits at the cost of moving slower and
+//! draining the guard meter. Running the meter dry breaks the guard and stuns the player.
+//! Raising the guard right before a hit lands parries it instead, repelling the weapon.
+
+use bevy::prelude::*;
+use leafwing_input_manager::prelude::*;
+
+use crate::{AppSystems, PausableSystems, duel::control::PlayerAction, screens::Screen};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_message::<GuardRaised>();
+    app.add_systems(
+        Update,
+        (
+            (tick_guard, tick_stun)
+                .in_set(AppSystems::TickTimers)
+                .in_set(PausableSystems),
+            update_guard
+                .in_set(AppSystems::RecordInput)
+                .in_set(PausableSystems),
+        )
+            .run_if(in_state(Screen::Gameplay)),
+    );
+}
+
+/// Knockback multiplier for hits taken while guarding.
+pub const GUARD_KNOCKBACK_MULTIPLIER: f32 = 0.3;
+/// Damage multiplier for hits taken while guarding.
+pub const GUARD_DAMAGE_MULTIPLIER: f32 = 0.5;
+/// Movement speed multiplier while guarding.
+pub const GUARD_SPEED_MULTIPLIER: f32 = 0.4;
+/// Guard meter lost per unit of strength of a guarded hit.
+pub const GUARD_COST_PER_STRENGTH: f32 = 0.0005;
+/// Speed a parried weapon is repelled with.
+pub const PARRY_REPEL_SPEED: f32 = 900.;
+
+/// Hits landing within this many seconds of raising the guard are parried.
+const PARRY_WINDOW_SECS: f32 = 0.15;
+/// Guard meter drained per second while guarding.
+const GUARD_DRAIN_PER_SEC: f32 = 0.35;
+/// Guard meter regained per second while not guarding.
+const GUARD_REGEN_PER_SEC: f32 = 0.25;
+const GUARD_BREAK_STUN_SECS: f32 = 1.5;
+
+#[derive(Component, Debug, Clone)]
+pub struct Guard {
+    /// Whether the guard is currently raised.
+    pub active: bool,
+    /// Remaining guard between 0 and 1.
+    pub meter: f32,
+    /// Seconds since the guard was last raised.
+    pub raised_for: f32,
+}
+
+impl Default for Guard {
+    fn default() -> Self {
+        Self {
+            active: false,
+            meter: 1.,
+            raised_for: 0.,
+        }
+    }
+}
+
+impl Guard {
+    /// Whether a hit landing right now would be parried.
+    pub fn is_parrying(&self) -> bool {
+        self.active && self.raised_for <= PARRY_WINDOW_SECS
+    }
+
+    /// Take a guarded hit of the given strength out of the meter.
+    pub fn absorb(&mut self, strength: f32) {
+        self.meter = (self.meter - strength * GUARD_COST_PER_STRENGTH).max(0.);
+    }
+}
+
+/// Written when a player raises their guard.
+#[derive(Message, Debug, Clone, Copy)]
+pub struct GuardRaised {
+    pub player: Entity,
+}
+
+/// A player that can't act until the timer runs out.
+#[derive(Component, Debug, Clone)]
+pub struct Stunned(pub Timer);
+
+fn update_guard(
+    mut players: Query<(Entity, &ActionState<PlayerAction>, &mut Guard), Without<Stunned>>,
+    mut guard_raised: MessageWriter<GuardRaised>,
+) {
+    for (entity, action_state, mut guard) in &mut players {
+        let raise = action_state.pressed(&PlayerAction::Gaurd) && guard.meter > 0.;
+        if raise && !guard.active {
+            guard.raised_for = 0.;
+            guard_raised.write(GuardRaised { player: entity });
+        }
+        guard.active = raise;
+    }
+}
+
+fn tick_guard(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut players: Query<(Entity, &mut Guard, Has<Stunned>)>,
+) {
+    let dt = time.delta_secs();
+    for (entity, mut guard, stunned) in &mut players {
+        if !guard.active {
+            if !stunned {
+                guard.meter = (guard.meter + GUARD_REGEN_PER_SEC * dt).min(1.);
+            }
+            continue;
+        }
+
+        guard.raised_for += dt;
+        guard.meter = (guard.meter - GUARD_DRAIN_PER_SEC * dt).max(0.);
+        if guard.meter <= 0. {
+            guard.active = false;
+            commands.entity(entity).insert(Stunned(Timer::from_seconds(
+                GUARD_BREAK_STUN_SECS,
+                TimerMode::Once,
+            )));
+        }
+    }
+}
+
+fn tick_stun(mut commands: Commands, time: Res<Time>, mut stunned: Query<(Entity, &mut Stunned)>) {
+    for (entity, mut stun) in &mut stunned {
+        stun.0.tick(time.delta());
+        if stun.0.is_finished() {
+            commands.entity(entity).remove::<Stunned>();
+        }
+    }
+}
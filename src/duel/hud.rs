@@ -0,0 +1,246 @@
+//! The in-game HUD, with a panel for every player along the top of the screen.
+//!
+//! Panels show a player's damage, round wins, dash charges and guard meter. Players are
+//! despawned when eliminated, so a panel without a player shows them as knocked out.
+
+use bevy::prelude::*;
+
+use crate::{
+    AppSystems,
+    duel::{
+        dash::Dash,
+        guard::{Guard, Stunned},
+        match_state::MatchScore,
+        player::{Damage, MatchConfig, PlayerID},
+    },
+    screens::Screen,
+    theme::{palette::*, prelude::*},
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(OnEnter(Screen::Gameplay), spawn_hud);
+    app.add_systems(
+        Update,
+        (
+            update_damage_text,
+            update_meters,
+            update_win_pips.run_if(resource_changed::<MatchScore>),
+        )
+            .in_set(AppSystems::Update)
+            .run_if(in_state(Screen::Gameplay)),
+    );
+}
+
+/// The text showing a player's damage percent.
+#[derive(Component, Debug)]
+struct DamageText(PlayerID);
+
+#[derive(Clone, Copy, Debug)]
+enum MeterKind {
+    Dash,
+    Guard,
+}
+
+/// The fill of a [`widget::meter`] showing one of a player's resources.
+#[derive(Component, Debug)]
+struct HudMeter {
+    player: PlayerID,
+    kind: MeterKind,
+}
+
+/// One of the pips counting a player's round wins.
+#[derive(Component, Debug)]
+struct WinPip {
+    player: PlayerID,
+    index: u32,
+    color: Color,
+}
+
+/// Damage at which the damage text is fully [`HIGH_DAMAGE_TEXT`].
+const HIGH_DAMAGE: f32 = 150.;
+const HIGH_DAMAGE_TEXT: Color = Color::srgb(0.9, 0.15, 0.1);
+const GUARD_METER: Color = Color::srgb(0.85, 0.85, 0.95);
+const STUNNED_GUARD_METER: Color = Color::srgb(0.5, 0.5, 0.55);
+const EMPTY_PIP: Color = Color::srgba(1., 1., 1., 0.15);
+
+fn spawn_hud(mut commands: Commands, config: Res<MatchConfig>) {
+    let panels = config
+        .players
+        .iter()
+        .enumerate()
+        .map(|(index, player)| player_panel(PlayerID(index), player.color, config.rounds_to_win))
+        .collect::<Vec<_>>();
+    commands.spawn((
+        Name::new("HUD"),
+        Node {
+            position_type: PositionType::Absolute,
+            width: percent(100),
+            top: px(16),
+            justify_content: JustifyContent::SpaceEvenly,
+            ..default()
+        },
+        // Stay below the pause overlay and menus.
+        GlobalZIndex(0),
+        Pickable::IGNORE,
+        DespawnOnExit(Screen::Gameplay),
+        Children::spawn(SpawnIter(panels.into_iter())),
+    ));
+}
+
+fn player_panel(id: PlayerID, color: Color, rounds_to_win: u32) -> impl Bundle {
+    (
+        Name::new("Player Panel"),
+        Node {
+            flex_direction: FlexDirection::Column,
+            align_items: AlignItems::Center,
+            padding: UiRect::all(px(10)),
+            row_gap: px(6),
+            ..default()
+        },
+        BackgroundColor(PANEL_BACKGROUND),
+        BorderRadius::all(px(8)),
+        Pickable::IGNORE,
+        children![
+            (
+                Name::new("Player Name"),
+                Node {
+                    align_items: AlignItems::Center,
+                    column_gap: px(8),
+                    ..default()
+                },
+                children![
+                    (
+                        Name::new("Player Color"),
+                        Node {
+                            width: px(16),
+                            height: px(16),
+                            ..default()
+                        },
+                        BackgroundColor(color),
+                        BorderRadius::MAX,
+                    ),
+                    widget::label(format!("P{}", id.0 + 1)),
+                ],
+            ),
+            (widget::header("0%"), DamageText(id)),
+            (
+                Name::new("Round Wins"),
+                Node {
+                    column_gap: px(4),
+                    ..default()
+                },
+                Children::spawn(SpawnIter((0..rounds_to_win).map(move |index| {
+                    (
+                        Name::new("Win Pip"),
+                        Node {
+                            width: px(12),
+                            height: px(12),
+                            ..default()
+                        },
+                        BackgroundColor(EMPTY_PIP),
+                        BorderRadius::MAX,
+                        WinPip {
+                            player: id,
+                            index,
+                            color,
+                        },
+                    )
+                }))),
+            ),
+            meter_row(
+                "Dash",
+                color,
+                HudMeter {
+                    player: id,
+                    kind: MeterKind::Dash,
+                },
+            ),
+            meter_row(
+                "Guard",
+                GUARD_METER,
+                HudMeter {
+                    player: id,
+                    kind: MeterKind::Guard,
+                },
+            ),
+        ],
+    )
+}
+
+fn meter_row(name: &'static str, color: Color, meter: HudMeter) -> impl Bundle {
+    (
+        Name::new(format!("{name} Meter")),
+        Node {
+            display: Display::Grid,
+            grid_template_columns: vec![GridTrack::px(40.), GridTrack::auto()],
+            align_items: AlignItems::Center,
+            ..default()
+        },
+        children![widget::caption(name), widget::meter(color, meter)],
+    )
+}
+
+fn update_damage_text(
+    players: Query<(&PlayerID, &Damage)>,
+    mut texts: Query<(&DamageText, &mut Text, &mut TextColor)>,
+) {
+    for (DamageText(id), mut text, mut color) in &mut texts {
+        let damage = players
+            .iter()
+            .find(|(player, _)| *player == id)
+            .map(|(_, damage)| damage.0);
+        let (new_text, new_color) = match damage {
+            Some(damage) => (
+                format!("{damage:.0}%"),
+                HEADER_TEXT.mix(&HIGH_DAMAGE_TEXT, (damage / HIGH_DAMAGE).min(1.)),
+            ),
+            None => ("KO".to_string(), HIGH_DAMAGE_TEXT),
+        };
+        text.set_if_neq(Text(new_text));
+        color.set_if_neq(TextColor(new_color));
+    }
+}
+
+fn update_meters(
+    players: Query<(&PlayerID, &Dash, &Guard, Has<Stunned>)>,
+    mut meters: Query<(&HudMeter, &mut Node, &mut BackgroundColor)>,
+) {
+    for (meter, mut node, mut background) in &mut meters {
+        let player = players.iter().find(|(id, ..)| **id == meter.player);
+        let fraction = match (meter.kind, player) {
+            (_, None) => 0.,
+            (MeterKind::Dash, Some((_, dash, ..))) => {
+                // Show progress towards the next charge as well.
+                let recharging = if dash.charges < dash.max_charges {
+                    dash.cooldown.fraction()
+                } else {
+                    0.
+                };
+                (dash.charges as f32 + recharging) / dash.max_charges as f32
+            }
+            (MeterKind::Guard, Some((_, _, guard, stunned))) => {
+                background.set_if_neq(BackgroundColor(if stunned {
+                    STUNNED_GUARD_METER
+                } else {
+                    GUARD_METER
+                }));
+                guard.meter
+            }
+        };
+        let width = percent(fraction * 100.);
+        if node.width != width {
+            node.width = width;
+        }
+    }
+}
+
+fn update_win_pips(score: Res<MatchScore>, mut pips: Query<(&WinPip, &mut BackgroundColor)>) {
+    for (pip, mut background) in &mut pips {
+        let wins = score.wins.get(pip.player.0).copied().unwrap_or(0);
+        background.set_if_neq(BackgroundColor(if pip.index < wins {
+            pip.color
+        } else {
+            EMPTY_PIP
+        }));
+    }
+}
@@ -0,0 +1,182 @@
+//! Rounds and the match they make up.
+//!
+//! Every round starts with a countdown, then players fight until at most one of them is
+//! left standing. The last player standing wins the round, and the first player to win
+//! [`MatchConfig::rounds_to_win`] rounds wins the match.
+
+use bevy::prelude::*;
+
+use crate::{
+    AppSystems, PausableSystems,
+    duel::{
+        arena::Eliminated,
+        player::{MatchConfig, PlayerID},
+    },
+    screens::Screen,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_sub_state::<MatchState>();
+    app.init_resource::<MatchScore>();
+
+    app.add_systems(OnEnter(Screen::Gameplay), reset_score);
+    app.add_systems(OnEnter(MatchState::Countdown), insert_timer(COUNTDOWN_SECS));
+    app.add_systems(
+        OnEnter(MatchState::RoundOver),
+        insert_timer(ROUND_OVER_SECS),
+    );
+    app.add_systems(
+        OnEnter(MatchState::MatchOver),
+        insert_timer(MATCH_OVER_SECS),
+    );
+    app.add_systems(OnExit(Screen::Gameplay), remove_timer);
+
+    app.add_systems(
+        Update,
+        (
+            tick_match_timer
+                .in_set(AppSystems::TickTimers)
+                .in_set(PausableSystems)
+                .run_if(resource_exists::<MatchTimer>),
+            (
+                start_fighting.run_if(in_state(MatchState::Countdown)),
+                // Players can still be knocked out during the countdown, which ends the round
+                // rather than starting the fight.
+                check_round_over
+                    .run_if(in_state(MatchState::Countdown).or(in_state(MatchState::Fighting))),
+                next_round.run_if(in_state(MatchState::RoundOver)),
+                leave_match.run_if(in_state(MatchState::MatchOver)),
+            )
+                .chain()
+                .in_set(AppSystems::Update),
+        ),
+    );
+}
+
+/// The phases of a match. Only exists while in [`Screen::Gameplay`].
+#[derive(SubStates, Copy, Clone, Eq, PartialEq, Hash, Debug, Default)]
+#[source(Screen = Screen::Gameplay)]
+pub enum MatchState {
+    /// Players have just been (re)spawned and the round is about to start.
+    #[default]
+    Countdown,
+    Fighting,
+    /// A round has just been decided.
+    RoundOver,
+    /// Someone has won enough rounds to win the match.
+    MatchOver,
+}
+
+/// Round wins of every player in the current match.
+#[derive(Resource, Debug, Clone, Default)]
+pub struct MatchScore {
+    /// Rounds won, indexed by [`PlayerID`].
+    pub wins: Vec<u32>,
+    /// The winner of the most recent round, or `None` if it was a draw.
+    pub round_winner: Option<PlayerID>,
+}
+
+impl MatchScore {
+    /// The player that has won the match, if any.
+    pub fn match_winner(&self, rounds_to_win: u32) -> Option<PlayerID> {
+        self.wins
+            .iter()
+            .position(|&wins| wins >= rounds_to_win)
+            .map(PlayerID)
+    }
+
+    /// Whether the next round could decide the match.
+    pub fn is_match_point(&self, rounds_to_win: u32) -> bool {
+        self.wins.iter().any(|&wins| wins + 1 >= rounds_to_win)
+    }
+}
+
+const COUNTDOWN_SECS: f32 = 3.0;
+const ROUND_OVER_SECS: f32 = 2.5;
+const MATCH_OVER_SECS: f32 = 4.0;
+
+/// Times the phases of a match that end on their own.
+#[derive(Resource, Debug, Clone, PartialEq, Reflect)]
+#[reflect(Resource)]
+pub struct MatchTimer(pub Timer);
+
+fn insert_timer(secs: f32) -> impl Fn(Commands) {
+    move |mut commands: Commands| {
+        commands.insert_resource(MatchTimer(Timer::from_seconds(secs, TimerMode::Once)));
+    }
+}
+
+fn remove_timer(mut commands: Commands) {
+    commands.remove_resource::<MatchTimer>();
+}
+
+fn tick_match_timer(time: Res<Time>, mut timer: ResMut<MatchTimer>) {
+    timer.0.tick(time.delta());
+}
+
+fn reset_score(mut score: ResMut<MatchScore>, config: Res<MatchConfig>) {
+    *score = MatchScore {
+        wins: vec![0; config.players.len()],
+        round_winner: None,
+    };
+}
+
+fn start_fighting(timer: Res<MatchTimer>, mut next_state: ResMut<NextState<MatchState>>) {
+    if timer.0.just_finished() {
+        next_state.set(MatchState::Fighting);
+    }
+}
+
+/// End the round once at most one player is left standing.
+fn check_round_over(
+    mut eliminated: MessageReader<Eliminated>,
+    players: Query<(Entity, &PlayerID)>,
+    mut score: ResMut<MatchScore>,
+    mut next_state: ResMut<NextState<MatchState>>,
+) {
+    // Eliminated players are despawned with commands, so they may still be around this frame.
+    let eliminated = eliminated
+        .read()
+        .map(|e| {
+            info!("Player {} was eliminated", e.id.0 + 1);
+            e.player
+        })
+        .collect::<Vec<_>>();
+    let alive = players
+        .iter()
+        .filter(|(entity, _)| !eliminated.contains(entity))
+        .map(|(_, &id)| id)
+        .collect::<Vec<_>>();
+    if alive.len() > 1 {
+        return;
+    }
+
+    let winner = alive.first().copied();
+    if let Some(PlayerID(index)) = winner {
+        score.wins[index] += 1;
+    }
+    score.round_winner = winner;
+    next_state.set(MatchState::RoundOver);
+}
+
+fn next_round(
+    timer: Res<MatchTimer>,
+    score: Res<MatchScore>,
+    config: Res<MatchConfig>,
+    mut next_state: ResMut<NextState<MatchState>>,
+) {
+    if !timer.0.just_finished() {
+        return;
+    }
+    next_state.set(if score.match_winner(config.rounds_to_win).is_some() {
+        MatchState::MatchOver
+    } else {
+        MatchState::Countdown
+    });
+}
+
+fn leave_match(timer: Res<MatchTimer>, mut next_screen: ResMut<NextState<Screen>>) {
+    if timer.0.just_finished() {
+        next_screen.set(Screen::Results);
+    }
+}
@@ -1,23 +1,53 @@
-mod control;
-mod player;
-mod weapon;
+pub mod ability;
+mod announcer;
+pub mod arena;
+pub mod bindings;
+pub mod control;
+mod dash;
+mod guard;
+mod hud;
+pub mod match_state;
+pub mod player;
+pub mod pointer;
+mod power_up;
+mod projectile;
+mod sfx;
+pub mod stats;
+mod tuning;
+pub mod weapon;
 
 use avian2d::prelude::*;
 use bevy::prelude::*;
 
-use crate::screens::Screen;
+use crate::{duel::tuning::Tuning, screens::Screen};
 
 pub(super) fn plugin(app: &mut App) {
-    app.add_plugins((player::plugin, control::plugin, PhysicsPlugins::default()))
-        .insert_resource(Gravity(Vec2::NEG_Y * 80.));
+    app.add_plugins((
+        ability::plugin,
+        announcer::plugin,
+        arena::plugin,
+        player::plugin,
+        control::plugin,
+        guard::plugin,
+        hud::plugin,
+        dash::plugin,
+        match_state::plugin,
+        weapon::plugin,
+        projectile::plugin,
+        pointer::plugin,
+        power_up::plugin,
+        sfx::plugin,
+        stats::plugin,
+    ));
+    app.add_plugins((tuning::plugin, PhysicsPlugins::default()));
 }
 
-pub fn dynamic_obj(radius: f32) -> impl Bundle {
+pub fn dynamic_obj(radius: f32, tuning: &Tuning) -> impl Bundle {
     (
         RigidBody::Dynamic,
         Collider::circle(radius),
-        Friction::new(0.7),
-        Restitution::new(0.2),
+        Friction::new(tuning.friction),
+        Restitution::new(tuning.restitution),
         TransformExtrapolation,
         TransformHermiteEasing,
         DespawnOnExit(Screen::Gameplay),
@@ -1,58 +1,214 @@
-use std::thread::spawn;
-
 use avian2d::prelude::*;
-use bevy::{input::gamepad::GamepadConnectionEvent, prelude::*};
-use leafwing_input_manager::prelude::*;
+use bevy::prelude::*;
 
 use crate::{
-    duel::{control::default_input_map, dynamic_obj, weapon::spawn_weapon},
-    screens::Screen,
+    duel::{
+        ability::{Ability, AbilityAssets, AbilityDef, AbilitySet},
+        arena::CurrentArena,
+        bindings::Bindings,
+        control::{InputDevice, MouseAim},
+        dash::Dash,
+        dynamic_obj,
+        guard::Guard,
+        match_state::MatchState,
+        pointer::PointerControls,
+        power_up::PowerUps,
+        tuning::Tuning,
+        weapon::{WeaponKind, spawn_weapon},
+    },
+    settings::Settings,
 };
 
 pub(super) fn plugin(app: &mut App) {
-    app.add_systems(OnEnter(Screen::Gameplay), spawn_player);
+    app.init_resource::<MatchConfig>();
+    app.add_systems(OnEnter(MatchState::Countdown), spawn_players);
 }
 
-#[derive(Component)]
-pub struct PlayerID(usize);
+/// The most players that can take part in a single match.
+pub const MAX_PLAYERS: usize = 4;
+
+/// The color of each player slot, indexed by [`PlayerID`].
+pub const PLAYER_COLORS: [Color; MAX_PLAYERS] = [
+    Color::srgb(0.3, 0.7, 0.9),
+    Color::srgb(0.9, 0.4, 0.3),
+    Color::srgb(0.4, 0.85, 0.4),
+    Color::srgb(0.95, 0.8, 0.3),
+];
+
+/// Horizontal distance between neighbouring spawn points, for arenas that don't have enough.
+const SPAWN_SPACING: f32 = 200.;
+
+#[derive(Component, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Reflect)]
+pub struct PlayerID(pub usize);
 
 #[derive(Component)]
 pub struct Speed(pub f32);
 
-pub(crate) fn spawn_player(
+/// How hurt a player is, in percent. Knockback from hits grows with it.
+#[derive(Component, Debug, Default)]
+pub struct Damage(pub f32);
+
+/// Marks an entity as belonging to a player, such as its weapon or the joint holding it.
+///
+/// Parts are despawned together with their player.
+#[derive(Component)]
+#[relationship(relationship_target = PlayerParts)]
+pub struct PlayerPart(pub Entity);
+
+#[derive(Component)]
+#[relationship_target(relationship = PlayerPart, linked_spawn)]
+pub struct PlayerParts(Vec<Entity>);
+
+/// Who takes part in the next match.
+#[derive(Resource, Clone, Debug)]
+pub struct MatchConfig {
+    /// One entry per player, in [`PlayerID`] order.
+    pub players: Vec<PlayerConfig>,
+    /// How many rounds a player has to win to win the match.
+    pub rounds_to_win: u32,
+    /// Index of the arena to fight in, into [`ArenaAssets::arenas`](crate::duel::arena::ArenaAssets).
+    pub arena: usize,
+}
+
+impl MatchConfig {
+    /// The device of the only player aiming with the mouse: the pointer player if there is one,
+    /// otherwise whoever is on the left half of the keyboard.
+    pub fn mouse_aim_device(&self) -> InputDevice {
+        if self
+            .players
+            .iter()
+            .any(|player| player.device == InputDevice::Pointer)
+        {
+            InputDevice::Pointer
+        } else {
+            InputDevice::KeyboardLeft
+        }
+    }
+}
+
+impl Default for MatchConfig {
+    /// Two players sharing the keyboard.
+    fn default() -> Self {
+        Self {
+            players: vec![
+                PlayerConfig {
+                    color: PLAYER_COLORS[0],
+                    weapon: WeaponKind::default(),
+                    ability: 0,
+                    device: InputDevice::KeyboardLeft,
+                },
+                PlayerConfig {
+                    color: PLAYER_COLORS[1],
+                    weapon: WeaponKind::default(),
+                    ability: 1,
+                    device: InputDevice::KeyboardRight,
+                },
+            ],
+            rounds_to_win: 3,
+            arena: 0,
+        }
+    }
+}
+
+/// The choices a single player made before the match.
+#[derive(Clone, Debug)]
+pub struct PlayerConfig {
+    pub color: Color,
+    pub weapon: WeaponKind,
+    /// Index into the abilities of [`AbilityAssets`].
+    pub ability: usize,
+    pub device: InputDevice,
+}
+
+/// Spread `count` players evenly along the x axis, centered on the origin.
+fn spawn_point(index: usize, count: usize) -> Vec2 {
+    let offset = index as f32 - (count - 1) as f32 / 2.;
+    Vec2::new(offset * SPAWN_SPACING, 0.)
+}
+
+fn spawn_players(
     mut commands: Commands,
     mut meshes: ResMut<Assets<Mesh>>,
     mut materials: ResMut<Assets<ColorMaterial>>,
+    config: Res<MatchConfig>,
+    arena: Res<CurrentArena>,
+    tuning: Res<Tuning>,
+    settings: Res<Settings>,
+    ability_assets: Res<AbilityAssets>,
+    ability_sets: Res<Assets<AbilitySet>>,
+) {
+    let abilities = ability_assets.abilities(&ability_sets);
+    let count = config.players.len();
+    let mouse_aim_device = config.mouse_aim_device();
+    for (index, player) in config.players.iter().enumerate() {
+        spawn_player(
+            &mut commands,
+            &mut meshes,
+            &mut materials,
+            &tuning,
+            &settings.bindings,
+            PlayerID(index),
+            player,
+            player.device == mouse_aim_device,
+            abilities.get(player.ability),
+            arena
+                .0
+                .spawn_points
+                .get(index)
+                .copied()
+                .unwrap_or_else(|| spawn_point(index, count)),
+        );
+    }
+}
+
+pub(crate) fn spawn_player(
+    commands: &mut Commands,
+    meshes: &mut ResMut<Assets<Mesh>>,
+    materials: &mut ResMut<Assets<ColorMaterial>>,
+    tuning: &Tuning,
+    bindings: &Bindings,
+    id: PlayerID,
+    config: &PlayerConfig,
+    mouse_aim: bool,
+    ability: Option<&AbilityDef>,
+    position: Vec2,
 ) {
     let player = commands
         .spawn((
-            PlayerID(0),
-            Speed(75000.),
-            MaxLinearSpeed(1000.),
-            Transform::default(),
-            Mesh2d(meshes.add(Mesh::from(Circle::new(15.)))),
-            MeshMaterial2d(materials.add(ColorMaterial::from(Color::srgb(0.3, 0.7, 0.9)))),
-            default_input_map(),
-            dynamic_obj(15.),
+            Name::new(format!("Player {}", id.0 + 1)),
+            id,
+            Speed(tuning.player.speed),
+            Damage::default(),
+            Guard::default(),
+            Dash::default(),
+            PowerUps::default(),
+            MaxLinearSpeed(tuning.player.max_speed),
+            Transform::from_translation(position.extend(0.)),
+            Mesh2d(meshes.add(Mesh::from(Circle::new(tuning.player.radius)))),
+            MeshMaterial2d(materials.add(ColorMaterial::from(config.color))),
+            bindings.input_map(config.device, id),
+            dynamic_obj(tuning.player.radius, tuning),
+            // Everyone is respawned at the start of the next round.
+            DespawnOnExit(MatchState::RoundOver),
         ))
         .id();
-    spawn_weapon(&mut commands, &mut meshes, &mut materials, player);
-}
-
-fn wrap_player_position(mut query: Query<&mut Transform, With<PlayerID>>, windows: Query<&Window>) {
-    let window = windows.single().unwrap();
-    let size = window.size();
-    for mut transform in query.iter_mut() {
-        if transform.translation.x > size.x / 2.0 {
-            transform.translation.x = -size.x / 2.0;
-        } else if transform.translation.x < -size.x / 2.0 {
-            transform.translation.x = size.x / 2.0;
-        }
-
-        if transform.translation.y > size.y / 2.0 {
-            transform.translation.y = -size.y / 2.0;
-        } else if transform.translation.y < -size.y / 2.0 {
-            transform.translation.y = size.y / 2.0;
-        }
+    if mouse_aim {
+        commands.entity(player).insert(MouseAim);
+    }
+    if config.device == InputDevice::Pointer {
+        commands.entity(player).insert(PointerControls);
+    }
+    if let Some(def) = ability {
+        commands.entity(player).insert(Ability::new(def.clone()));
     }
+    spawn_weapon(
+        commands,
+        meshes,
+        materials,
+        tuning,
+        config.weapon,
+        player,
+        position,
+        config.color,
+    );
 }
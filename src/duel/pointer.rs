@@ -0,0 +1,305 @@
+//! Mouse and touch controls, for players without a keyboard half or gamepad to themselves.
+//!
+//! Holding the left mouse button moves the player towards the cursor. On touch screens, a
+//! finger on the left half of the screen acts as a virtual joystick. Both get on-screen buttons
+//! for the rest of the [`PlayerAction`]s.
+
+use bevy::{prelude::*, window::PrimaryWindow};
+use leafwing_input_manager::prelude::*;
+
+use crate::{
+    AppSystems, PausableSystems,
+    duel::{
+        control::{InputDevice, PlayerAction, cursor_position},
+        player::MatchConfig,
+    },
+    screens::Screen,
+    theme::palette::*,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<VirtualJoystick>();
+    app.add_observer(hold_virtual_button);
+    app.add_observer(release_virtual_button);
+    app.add_observer(end_virtual_button_drag);
+    app.add_systems(
+        OnEnter(Screen::Gameplay),
+        spawn_touch_controls.run_if(has_pointer_player),
+    );
+    app.add_systems(OnExit(Screen::Gameplay), reset_joystick);
+    app.add_systems(
+        Update,
+        (
+            (track_joystick, move_with_pointer, press_virtual_buttons)
+                .chain()
+                .in_set(AppSystems::RecordInput)
+                .in_set(PausableSystems),
+            (update_joystick_ui, update_virtual_button_colors).in_set(AppSystems::Update),
+        )
+            .run_if(in_state(Screen::Gameplay)),
+    );
+}
+
+/// Marks a player controlled with the mouse or a touch screen.
+#[derive(Component, Debug)]
+pub struct PointerControls;
+
+/// The finger currently held down as a joystick, if any.
+#[derive(Resource, Debug, Default)]
+struct VirtualJoystick {
+    touch: Option<u64>,
+    /// Where the finger first touched the screen, in logical pixels.
+    origin: Vec2,
+}
+
+/// An on-screen button standing in for a [`PlayerAction`].
+#[derive(Component, Debug)]
+struct VirtualButton {
+    action: PlayerAction,
+    held: bool,
+}
+
+#[derive(Component)]
+struct JoystickBase;
+
+#[derive(Component)]
+struct JoystickKnob;
+
+/// How far a finger has to move from where it touched down to move at full speed.
+const JOYSTICK_RADIUS: f32 = 60.;
+/// How far the cursor has to be from the player to move at full speed.
+const MOUSE_MOVE_RANGE: f32 = 150.;
+const KNOB_RADIUS: f32 = 25.;
+/// [`BUTTON_BACKGROUND`], see-through.
+const VIRTUAL_BUTTON_BACKGROUND: Color = Color::srgba(0.275, 0.400, 0.750, 0.6);
+/// [`BUTTON_PRESSED_BACKGROUND`], see-through.
+const VIRTUAL_BUTTON_HELD_BACKGROUND: Color = Color::srgba(0.239, 0.286, 0.600, 0.8);
+const VIRTUAL_BUTTONS: [(PlayerAction, &str); 4] = [
+    (PlayerAction::Dash, "Dash"),
+    (PlayerAction::Gaurd, "Guard"),
+    (PlayerAction::Fire, "Fire"),
+    (PlayerAction::Ability, "Ability"),
+];
+
+fn has_pointer_player(config: Res<MatchConfig>) -> bool {
+    config
+        .players
+        .iter()
+        .any(|player| player.device == InputDevice::Pointer)
+}
+
+fn reset_joystick(mut joystick: ResMut<VirtualJoystick>) {
+    *joystick = VirtualJoystick::default();
+}
+
+fn spawn_touch_controls(mut commands: Commands) {
+    let joystick_circle = |name: &'static str, radius: f32, color: Color| {
+        (
+            Name::new(name),
+            Node {
+                position_type: PositionType::Absolute,
+                width: px(radius * 2.),
+                height: px(radius * 2.),
+                ..default()
+            },
+            BackgroundColor(color),
+            BorderRadius::MAX,
+            Visibility::Hidden,
+            Pickable::IGNORE,
+            DespawnOnExit(Screen::Gameplay),
+        )
+    };
+    commands.spawn((
+        joystick_circle(
+            "Joystick Base",
+            JOYSTICK_RADIUS,
+            Color::srgba(1., 1., 1., 0.1),
+        ),
+        JoystickBase,
+    ));
+    commands.spawn((
+        joystick_circle("Joystick Knob", KNOB_RADIUS, Color::srgba(1., 1., 1., 0.3)),
+        JoystickKnob,
+    ));
+
+    commands.spawn((
+        Name::new("Virtual Buttons"),
+        Node {
+            position_type: PositionType::Absolute,
+            right: px(30),
+            bottom: px(30),
+            display: Display::Grid,
+            grid_template_columns: RepeatedGridTrack::px(2, 100.),
+            row_gap: px(16),
+            column_gap: px(16),
+            ..default()
+        },
+        Pickable::IGNORE,
+        DespawnOnExit(Screen::Gameplay),
+        Children::spawn(SpawnIter(
+            VIRTUAL_BUTTONS
+                .into_iter()
+                .map(|(action, text)| virtual_button(action, text)),
+        )),
+    ));
+}
+
+/// Virtual buttons aren't UI [`Button`]s, so they don't make menu sounds every time they're
+/// touched.
+fn virtual_button(action: PlayerAction, text: &'static str) -> impl Bundle {
+    (
+        Name::new("Virtual Button"),
+        VirtualButton {
+            action,
+            held: false,
+        },
+        Node {
+            width: px(100),
+            height: px(100),
+            align_items: AlignItems::Center,
+            justify_content: JustifyContent::Center,
+            ..default()
+        },
+        BackgroundColor(VIRTUAL_BUTTON_BACKGROUND),
+        BorderRadius::MAX,
+        children![(
+            Name::new("Button Text"),
+            Text::new(text),
+            TextFont::from_font_size(20.0),
+            TextColor(BUTTON_TEXT),
+            Pickable::IGNORE,
+        )],
+    )
+}
+
+// Picking tracks every finger separately, so buttons can be held while using the joystick.
+// A finger sliding off a button before lifting only ends a drag, so listen for that as well.
+fn hold_virtual_button(press: On<Pointer<Press>>, mut buttons: Query<&mut VirtualButton>) {
+    if let Ok(mut button) = buttons.get_mut(press.entity) {
+        button.held = true;
+    }
+}
+
+fn release_virtual_button(release: On<Pointer<Release>>, mut buttons: Query<&mut VirtualButton>) {
+    if let Ok(mut button) = buttons.get_mut(release.entity) {
+        button.held = false;
+    }
+}
+
+fn end_virtual_button_drag(drag_end: On<Pointer<DragEnd>>, mut buttons: Query<&mut VirtualButton>) {
+    if let Ok(mut button) = buttons.get_mut(drag_end.entity) {
+        button.held = false;
+    }
+}
+
+fn track_joystick(
+    touches: Res<Touches>,
+    window: Single<&Window, With<PrimaryWindow>>,
+    mut joystick: ResMut<VirtualJoystick>,
+) {
+    if let Some(id) = joystick.touch
+        && touches.get_pressed(id).is_none()
+    {
+        joystick.touch = None;
+    }
+    if joystick.touch.is_some() {
+        return;
+    }
+    // The right half of the screen is left for the buttons.
+    if let Some(touch) = touches
+        .iter_just_pressed()
+        .find(|touch| touch.position().x < window.width() / 2.)
+    {
+        joystick.touch = Some(touch.id());
+        joystick.origin = touch.position();
+    }
+}
+
+fn move_with_pointer(
+    touches: Res<Touches>,
+    mouse: Res<ButtonInput<MouseButton>>,
+    joystick: Res<VirtualJoystick>,
+    window: Single<&Window, With<PrimaryWindow>>,
+    camera: Single<(&Camera, &GlobalTransform)>,
+    buttons: Query<&VirtualButton>,
+    mut players: Query<(&mut ActionState<PlayerAction>, &GlobalTransform), With<PointerControls>>,
+) {
+    let joystick = joystick
+        .touch
+        .and_then(|id| touches.get_pressed(id))
+        .map(|touch| {
+            // Screen coordinates grow downwards.
+            let offset = touch.position() - joystick.origin;
+            (Vec2::new(offset.x, -offset.y) / JOYSTICK_RADIUS).clamp_length_max(1.)
+        });
+    let clicking_button = buttons.iter().any(|button| button.held);
+    let cursor = cursor_position(&window, &camera)
+        .filter(|_| mouse.pressed(MouseButton::Left) && !clicking_button);
+
+    for (mut action_state, transform) in &mut players {
+        let movement = match (joystick, cursor) {
+            (Some(movement), _) => movement,
+            (None, Some(cursor)) => ((cursor - transform.translation().truncate())
+                / MOUSE_MOVE_RANGE)
+                .clamp_length_max(1.),
+            (None, None) => Vec2::ZERO,
+        };
+        action_state.set_axis_pair(&PlayerAction::Move, movement);
+    }
+}
+
+fn press_virtual_buttons(
+    buttons: Query<&VirtualButton>,
+    mut players: Query<&mut ActionState<PlayerAction>, With<PointerControls>>,
+) {
+    for mut action_state in &mut players {
+        for button in &buttons {
+            if button.held {
+                action_state.press(&button.action);
+            } else {
+                action_state.release(&button.action);
+            }
+        }
+    }
+}
+
+fn update_virtual_button_colors(
+    mut buttons: Query<(&VirtualButton, &mut BackgroundColor), Changed<VirtualButton>>,
+) {
+    for (button, mut background) in &mut buttons {
+        background.0 = if button.held {
+            VIRTUAL_BUTTON_HELD_BACKGROUND
+        } else {
+            VIRTUAL_BUTTON_BACKGROUND
+        };
+    }
+}
+
+fn update_joystick_ui(
+    joystick: Res<VirtualJoystick>,
+    touches: Res<Touches>,
+    mut circles: Query<
+        (&mut Node, &mut Visibility, Has<JoystickKnob>),
+        Or<(With<JoystickBase>, With<JoystickKnob>)>,
+    >,
+) {
+    let touch = joystick.touch.and_then(|id| touches.get_pressed(id));
+    for (mut node, mut visibility, is_knob) in &mut circles {
+        let Some(touch) = touch else {
+            *visibility = Visibility::Hidden;
+            continue;
+        };
+        let (center, radius) = if is_knob {
+            let offset = touch.position() - joystick.origin;
+            (
+                joystick.origin + offset.clamp_length_max(JOYSTICK_RADIUS),
+                KNOB_RADIUS,
+            )
+        } else {
+            (joystick.origin, JOYSTICK_RADIUS)
+        };
+        node.left = px(center.x - radius);
+        node.top = px(center.y - radius);
+        *visibility = Visibility::Inherited;
+    }
+}
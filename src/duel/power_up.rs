@@ -0,0 +1,358 @@
+//! Power-ups that show up around the arena during a round.
+//!
+//! Picking one up temporarily scales some of the player's stats. Picking up more of the same
+//! kind stacks their effect up to a limit and restarts the timer. Stats that come from the
+//! [`Tuning`] file are recomputed from it, so hot reloads keep power-ups in effect.
+
+use avian2d::prelude::*;
+use bevy::{ecs::system::SystemParam, prelude::*};
+use rand::seq::IndexedRandom;
+
+use crate::{
+    AppSystems, PausableSystems,
+    duel::{
+        arena::CurrentArena,
+        match_state::MatchState,
+        player::{PlayerID, PlayerParts, Speed},
+        tuning::Tuning,
+        weapon::{ChainJoint, WeaponBody},
+    },
+    screens::Screen,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(OnEnter(MatchState::Countdown), spawn_spawners);
+    app.add_systems(
+        Update,
+        (
+            tick_spawners
+                .run_if(in_state(MatchState::Fighting))
+                .in_set(AppSystems::TickTimers),
+            tick_power_ups.in_set(AppSystems::TickTimers),
+            collect_pickups.in_set(AppSystems::Update),
+        )
+            .in_set(PausableSystems)
+            .run_if(in_state(Screen::Gameplay)),
+    );
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Reflect)]
+pub enum PowerUpKind {
+    /// Move faster.
+    Haste,
+    /// A denser weapon that hits harder but is harder to swing.
+    HeavyWeapon,
+    /// A bigger weapon.
+    BigWeapon,
+    /// More distance between the player and their weapon.
+    LongReach,
+}
+
+impl PowerUpKind {
+    const ALL: [Self; 4] = [
+        Self::Haste,
+        Self::HeavyWeapon,
+        Self::BigWeapon,
+        Self::LongReach,
+    ];
+
+    /// The stats scaled by a single stack of this power-up.
+    fn modifiers(self) -> PowerUpModifiers {
+        let none = PowerUpModifiers::default();
+        match self {
+            Self::Haste => PowerUpModifiers { speed: 1.4, ..none },
+            Self::HeavyWeapon => PowerUpModifiers {
+                weapon_mass: 1.8,
+                ..none
+            },
+            Self::BigWeapon => PowerUpModifiers {
+                weapon_size: 1.3,
+                ..none
+            },
+            Self::LongReach => PowerUpModifiers {
+                reach: 1.35,
+                ..none
+            },
+        }
+    }
+
+    fn max_stacks(self) -> u32 {
+        match self {
+            Self::HeavyWeapon => 3,
+            _ => 2,
+        }
+    }
+
+    fn duration_secs(self) -> f32 {
+        match self {
+            Self::Haste => 6.,
+            _ => 10.,
+        }
+    }
+
+    fn color(self) -> Color {
+        match self {
+            Self::Haste => Color::srgb(0.4, 0.9, 1.0),
+            Self::HeavyWeapon => Color::srgb(0.6, 0.45, 0.3),
+            Self::BigWeapon => Color::srgb(1.0, 0.6, 0.2),
+            Self::LongReach => Color::srgb(0.75, 0.5, 1.0),
+        }
+    }
+}
+
+/// Multipliers applied to a player's stats.
+///
+/// On a player, the combined effect of all their power-ups.
+#[derive(Component, Clone, Copy, Debug)]
+pub struct PowerUpModifiers {
+    /// Movement force and top speed.
+    pub speed: f32,
+    /// Density of the weapon's colliders.
+    pub weapon_mass: f32,
+    /// Scale of the weapon.
+    pub weapon_size: f32,
+    /// Length of the joints holding the weapon.
+    pub reach: f32,
+}
+
+impl Default for PowerUpModifiers {
+    fn default() -> Self {
+        Self {
+            speed: 1.,
+            weapon_mass: 1.,
+            weapon_size: 1.,
+            reach: 1.,
+        }
+    }
+}
+
+impl PowerUpModifiers {
+    /// These modifiers applied `stacks` times, or undone if `stacks` is negative.
+    fn powi(self, stacks: i32) -> Self {
+        Self {
+            speed: self.speed.powi(stacks),
+            weapon_mass: self.weapon_mass.powi(stacks),
+            weapon_size: self.weapon_size.powi(stacks),
+            reach: self.reach.powi(stacks),
+        }
+    }
+
+    /// The effect of both these and `other` modifiers.
+    fn stack(self, other: Self) -> Self {
+        Self {
+            speed: self.speed * other.speed,
+            weapon_mass: self.weapon_mass * other.weapon_mass,
+            weapon_size: self.weapon_size * other.weapon_size,
+            reach: self.reach * other.reach,
+        }
+    }
+}
+
+/// The power-ups a player has picked up and not yet run out of.
+#[derive(Component, Debug, Default)]
+#[require(PowerUpModifiers)]
+pub struct PowerUps(pub Vec<ActivePowerUp>);
+
+#[derive(Debug)]
+pub struct ActivePowerUp {
+    pub kind: PowerUpKind,
+    pub stacks: u32,
+    /// Time until the power-up wears off.
+    pub timer: Timer,
+}
+
+/// Spawns a random power-up every now and then, as long as its last one has been picked up.
+#[derive(Component, Debug)]
+struct PowerUpSpawner {
+    timer: Timer,
+    pickup: Option<Entity>,
+}
+
+#[derive(Component, Debug)]
+#[require(Sensor, CollisionEventsEnabled)]
+struct PowerUpPickup(PowerUpKind);
+
+const SPAWN_INTERVAL_SECS: f32 = 8.;
+const PICKUP_RADIUS: f32 = 14.;
+
+fn spawn_spawners(mut commands: Commands, arena: Res<CurrentArena>) {
+    for &position in &arena.0.power_up_spawns {
+        commands.spawn((
+            Name::new("Power-Up Spawner"),
+            PowerUpSpawner {
+                timer: Timer::from_seconds(SPAWN_INTERVAL_SECS, TimerMode::Repeating),
+                pickup: None,
+            },
+            Transform::from_translation(position.extend(0.)),
+            DespawnOnExit(MatchState::RoundOver),
+        ));
+    }
+}
+
+fn tick_spawners(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    mut spawners: Query<(&mut PowerUpSpawner, &Transform)>,
+    pickups: Query<(), With<PowerUpPickup>>,
+) {
+    for (mut spawner, transform) in &mut spawners {
+        if spawner
+            .pickup
+            .is_some_and(|pickup| pickups.contains(pickup))
+        {
+            continue;
+        }
+        spawner.timer.tick(time.delta());
+        if !spawner.timer.just_finished() {
+            continue;
+        }
+
+        let Some(&kind) = PowerUpKind::ALL.choose(&mut rand::rng()) else {
+            continue;
+        };
+        let pickup = commands
+            .spawn((
+                Name::new("Power-Up"),
+                PowerUpPickup(kind),
+                RigidBody::Static,
+                Collider::circle(PICKUP_RADIUS),
+                Mesh2d(meshes.add(Circle::new(PICKUP_RADIUS))),
+                MeshMaterial2d(materials.add(kind.color())),
+                *transform,
+                DespawnOnExit(MatchState::RoundOver),
+            ))
+            .id();
+        spawner.pickup = Some(pickup);
+    }
+}
+
+fn collect_pickups(
+    mut commands: Commands,
+    mut collisions: MessageReader<CollisionStart>,
+    pickups: Query<&PowerUpPickup>,
+    mut players: Query<&mut PowerUps, With<PlayerID>>,
+    mut targets: ModifierTargets,
+    mut collected: Local<Vec<Entity>>,
+) {
+    collected.clear();
+    for event in collisions.read() {
+        for (pickup, player) in [
+            (event.collider1, event.collider2),
+            (event.collider2, event.collider1),
+        ] {
+            let (Ok(&PowerUpPickup(kind)), Ok(mut power_ups)) =
+                (pickups.get(pickup), players.get_mut(player))
+            else {
+                continue;
+            };
+            // Whoever touches a pickup first gets it.
+            if collected.contains(&pickup) {
+                continue;
+            }
+            collected.push(pickup);
+            commands.entity(pickup).despawn();
+
+            let timer = Timer::from_seconds(kind.duration_secs(), TimerMode::Once);
+            match power_ups.0.iter_mut().find(|active| active.kind == kind) {
+                Some(active) => {
+                    active.timer = timer;
+                    if active.stacks < kind.max_stacks() {
+                        active.stacks += 1;
+                        targets.apply(player, kind.modifiers());
+                    }
+                }
+                None => {
+                    power_ups.0.push(ActivePowerUp {
+                        kind,
+                        stacks: 1,
+                        timer,
+                    });
+                    targets.apply(player, kind.modifiers());
+                }
+            }
+        }
+    }
+}
+
+fn tick_power_ups(
+    time: Res<Time>,
+    mut players: Query<(Entity, &mut PowerUps)>,
+    mut targets: ModifierTargets,
+) {
+    for (player, mut power_ups) in &mut players {
+        power_ups.0.retain_mut(|active| {
+            active.timer.tick(time.delta());
+            if !active.timer.is_finished() {
+                return true;
+            }
+            targets.apply(
+                player,
+                active.kind.modifiers().powi(-(active.stacks as i32)),
+            );
+            false
+        });
+    }
+}
+
+/// Everything about a player that power-ups can change.
+#[derive(SystemParam)]
+struct ModifierTargets<'w, 's> {
+    tuning: Res<'w, Tuning>,
+    players: Query<
+        'w,
+        's,
+        (
+            &'static mut PowerUpModifiers,
+            &'static mut Speed,
+            &'static mut MaxLinearSpeed,
+            &'static PlayerParts,
+        ),
+    >,
+    weapon_bodies: Query<
+        'w,
+        's,
+        (
+            &'static mut Transform,
+            &'static mut ColliderDensity,
+            Option<&'static Children>,
+        ),
+        With<WeaponBody>,
+    >,
+    weapon_colliders: Query<'w, 's, &'static mut ColliderDensity, Without<WeaponBody>>,
+    chain_joints: Query<'w, 's, &'static mut DistanceJoint, With<ChainJoint>>,
+    pivot_joints: Query<'w, 's, &'static mut RevoluteJoint>,
+}
+
+impl ModifierTargets<'_, '_> {
+    fn apply(&mut self, player: Entity, modifiers: PowerUpModifiers) {
+        let Ok((mut total, mut speed, mut max_speed, parts)) = self.players.get_mut(player) else {
+            return;
+        };
+        *total = total.stack(modifiers);
+        speed.0 = self.tuning.player.speed * total.speed;
+        max_speed.0 = self.tuning.player.max_speed * total.speed;
+
+        for part in parts.iter() {
+            if let Ok((mut transform, mut density, children)) = self.weapon_bodies.get_mut(part) {
+                transform.scale *= modifiers.weapon_size;
+                density.0 *= modifiers.weapon_mass;
+                // Some weapons hit with a collider of their own, like a hammer's head.
+                for &child in children.into_iter().flatten() {
+                    if let Ok(mut density) = self.weapon_colliders.get_mut(child) {
+                        density.0 *= modifiers.weapon_mass;
+                    }
+                }
+            }
+            if let Ok(mut joint) = self.chain_joints.get_mut(part) {
+                joint.limits.max = self.tuning.weapon.chain_link_length * total.reach;
+            }
+            if let Ok(mut joint) = self.pivot_joints.get_mut(part)
+                && let Some(anchor) = joint.local_anchor2()
+            {
+                *joint = joint.clone().with_local_anchor2(anchor * modifiers.reach);
+            }
+        }
+    }
+}
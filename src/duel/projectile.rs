@@ -0,0 +1,176 @@
+//! Ranged weapons and the projectiles they fire.
+
+use avian2d::prelude::*;
+use bevy::prelude::*;
+use leafwing_input_manager::prelude::*;
+
+use crate::{
+    AppSystems, PausableSystems,
+    duel::{
+        control::PlayerAction, dynamic_obj, player::PlayerPart, tuning::Tuning, weapon::Weapon,
+    },
+    screens::Screen,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(
+        Update,
+        (
+            (tick_launchers, tick_projectiles)
+                .in_set(AppSystems::TickTimers)
+                .in_set(PausableSystems),
+            (fire_launchers, despawn_projectiles)
+                .in_set(AppSystems::Update)
+                .in_set(PausableSystems),
+        )
+            .run_if(in_state(Screen::Gameplay)),
+    );
+}
+
+const PROJECTILE_RADIUS: f32 = 6.;
+const PROJECTILE_SPEED: f32 = 900.;
+const PROJECTILE_LIFETIME_SECS: f32 = 1.5;
+/// Projectiles fly in a flatter arc than everything else.
+const PROJECTILE_GRAVITY_SCALE: f32 = 0.2;
+const PROJECTILE_COLOR: Color = Color::srgb(1.0, 0.9, 0.5);
+
+/// Fires projectiles from the tip of the weapon it's on when its owner presses
+/// [`PlayerAction::Fire`].
+///
+/// The weapon points along its local y axis.
+#[derive(Component, Debug, Clone)]
+pub struct Launcher {
+    /// Shots left before having to reload.
+    pub ammo: u32,
+    pub max_ammo: u32,
+    /// Distance from the weapon's center to its tip.
+    pub muzzle: f32,
+    /// Minimum time between two shots.
+    pub cooldown: Timer,
+    /// Time to reload a single shot. Only ticks while not at full ammo.
+    pub reload: Timer,
+}
+
+impl Launcher {
+    pub fn new(muzzle: f32) -> Self {
+        let mut cooldown = Timer::from_seconds(0.3, TimerMode::Once);
+        cooldown.finish();
+        Self {
+            ammo: 3,
+            max_ammo: 3,
+            muzzle,
+            cooldown,
+            reload: Timer::from_seconds(1.5, TimerMode::Repeating),
+        }
+    }
+}
+
+/// A short-lived projectile. Hits opposing players like any other [`Weapon`].
+#[derive(Component, Debug, Clone)]
+pub struct Projectile {
+    pub lifetime: Timer,
+}
+
+/// A projectile fired by `owner`, flying with the given velocity.
+pub fn projectile(owner: Entity, velocity: Vec2, tuning: &Tuning) -> impl Bundle {
+    (
+        Name::new("Projectile"),
+        Projectile {
+            lifetime: Timer::from_seconds(PROJECTILE_LIFETIME_SECS, TimerMode::Once),
+        },
+        Weapon,
+        PlayerPart(owner),
+        dynamic_obj(PROJECTILE_RADIUS, tuning),
+        LinearVelocity(velocity),
+        GravityScale(PROJECTILE_GRAVITY_SCALE),
+        SweptCcd::default(),
+    )
+}
+
+fn tick_launchers(time: Res<Time>, mut launchers: Query<&mut Launcher>) {
+    for mut launcher in &mut launchers {
+        launcher.cooldown.tick(time.delta());
+        if launcher.ammo < launcher.max_ammo {
+            launcher.reload.tick(time.delta());
+            if launcher.reload.just_finished() {
+                launcher.ammo += 1;
+            }
+        }
+    }
+}
+
+fn tick_projectiles(time: Res<Time>, mut projectiles: Query<&mut Projectile>) {
+    for mut projectile in &mut projectiles {
+        projectile.lifetime.tick(time.delta());
+    }
+}
+
+fn fire_launchers(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    mut launchers: Query<(
+        &PlayerPart,
+        &mut Launcher,
+        &Position,
+        &Rotation,
+        &LinearVelocity,
+    )>,
+    players: Query<&ActionState<PlayerAction>>,
+    tuning: Res<Tuning>,
+) {
+    for (&PlayerPart(owner), mut launcher, position, rotation, velocity) in &mut launchers {
+        let Ok(action_state) = players.get(owner) else {
+            continue;
+        };
+        if !action_state.just_pressed(&PlayerAction::Fire)
+            || !launcher.cooldown.is_finished()
+            || launcher.ammo == 0
+        {
+            continue;
+        }
+
+        launcher.ammo -= 1;
+        launcher.cooldown.reset();
+
+        let direction = *rotation * Vec2::Y;
+        let muzzle = position.0 + direction * (launcher.muzzle + PROJECTILE_RADIUS + 2.);
+        commands.spawn((
+            projectile(owner, velocity.0 + direction * PROJECTILE_SPEED, &tuning),
+            Mesh2d(meshes.add(Circle::new(PROJECTILE_RADIUS))),
+            MeshMaterial2d(materials.add(PROJECTILE_COLOR)),
+            Transform::from_translation(muzzle.extend(0.)),
+        ));
+    }
+}
+
+/// Projectiles break on whatever solid thing they touch first, or when they run out of time.
+fn despawn_projectiles(
+    mut commands: Commands,
+    mut collisions: MessageReader<CollisionStart>,
+    projectiles: Query<(Entity, &Projectile)>,
+    sensors: Query<(), With<Sensor>>,
+) {
+    let mut expired = projectiles
+        .iter()
+        .filter(|(_, projectile)| projectile.lifetime.is_finished())
+        .map(|(entity, _)| entity)
+        .collect::<Vec<_>>();
+    for event in collisions.read() {
+        for (collider, other) in [
+            (event.collider1, event.collider2),
+            (event.collider2, event.collider1),
+        ] {
+            // Fly through pickups and kill zones.
+            if projectiles.contains(collider) && !sensors.contains(other) {
+                expired.push(collider);
+            }
+        }
+    }
+
+    expired.sort();
+    expired.dedup();
+    for entity in expired {
+        commands.entity(entity).try_despawn();
+    }
+}
@@ -0,0 +1,179 @@
+//! Gameplay sound effects.
+//!
+//! Impacts are picked up from the physics engine and get louder the harder things collide.
+//! Impacts and dashes are spatial, so they pan and fade with where they happen in the arena.
+//! Every sound picks a random variant and pitch so that repeated sounds don't get grating.
+
+use avian2d::prelude::*;
+use bevy::{audio::Volume, prelude::*};
+use rand::{Rng, seq::IndexedRandom};
+
+use crate::{
+    AppSystems, PausableSystems,
+    asset_tracking::LoadResource,
+    audio::{sound_effect, spatial_sound_effect, varied_sound_effect},
+    duel::{
+        arena::Eliminated,
+        dash::DashStarted,
+        guard::{Guard, GuardRaised, Stunned},
+        player::PlayerID,
+        weapon::WeaponBody,
+    },
+    screens::Screen,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.load_resource::<SfxAssets>();
+    app.add_systems(
+        Update,
+        (impact_sounds, dash_sounds, guard_sounds, elimination_sounds)
+            .in_set(AppSystems::Update)
+            .in_set(PausableSystems)
+            .run_if(in_state(Screen::Gameplay).and(resource_exists::<SfxAssets>)),
+    );
+}
+
+// Until dedicated sounds exist, these reuse the footstep and button sounds at different pitches.
+#[derive(Resource, Asset, Clone, Reflect)]
+#[reflect(Resource)]
+struct SfxAssets {
+    #[dependency]
+    impacts: Vec<Handle<AudioSource>>,
+    #[dependency]
+    guard: Handle<AudioSource>,
+    #[dependency]
+    elimination: Handle<AudioSource>,
+}
+
+impl FromWorld for SfxAssets {
+    fn from_world(world: &mut World) -> Self {
+        let assets = world.resource::<AssetServer>();
+        Self {
+            impacts: vec![
+                assets.load("audio/sound_effects/step1.ogg"),
+                assets.load("audio/sound_effects/step2.ogg"),
+                assets.load("audio/sound_effects/step3.ogg"),
+                assets.load("audio/sound_effects/step4.ogg"),
+            ],
+            guard: assets.load("audio/sound_effects/button_click.ogg"),
+            elimination: assets.load("audio/sound_effects/button_hover.ogg"),
+        }
+    }
+}
+
+/// Impacts softer than this are silent.
+const MIN_IMPACT_IMPULSE: f32 = 20_000.;
+/// Impacts at least this hard play at full volume.
+const FULL_IMPACT_IMPULSE: f32 = 400_000.;
+/// Chains and bodies resting against each other start lots of contacts, so cap how many impacts
+/// are heard at once.
+const MAX_IMPACTS_PER_FRAME: usize = 3;
+/// How far pitch can randomly stray from normal, as a fraction.
+const PITCH_VARIATION: f32 = 0.1;
+
+/// A random playback speed around `speed`.
+fn vary_pitch(speed: f32) -> f32 {
+    speed * rand::rng().random_range(1. - PITCH_VARIATION..1. + PITCH_VARIATION)
+}
+
+fn impact_sounds(
+    mut commands: Commands,
+    mut collision_starts: MessageReader<CollisionStart>,
+    collisions: Collisions,
+    audible: Query<(), Or<(With<PlayerID>, With<WeaponBody>)>>,
+    sfx_assets: Res<SfxAssets>,
+    mut impacts: Local<Vec<(f32, Vec2)>>,
+) {
+    impacts.clear();
+    for event in collision_starts.read() {
+        let (Some(body1), Some(body2)) = (event.body1, event.body2) else {
+            continue;
+        };
+        if !audible.contains(body1) && !audible.contains(body2) {
+            continue;
+        }
+        let Some(contacts) = collisions.get(event.collider1, event.collider2) else {
+            continue;
+        };
+        let impulse = contacts.total_normal_impulse_magnitude();
+        if impulse >= MIN_IMPACT_IMPULSE
+            && let Some(contact) = contacts.find_deepest_contact()
+        {
+            impacts.push((impulse, contact.point));
+        }
+    }
+
+    impacts.sort_by(|(a, _), (b, _)| b.total_cmp(a));
+    for &(impulse, position) in impacts.iter().take(MAX_IMPACTS_PER_FRAME) {
+        let Some(handle) = sfx_assets.impacts.choose(&mut rand::rng()) else {
+            return;
+        };
+        let loudness = (impulse / FULL_IMPACT_IMPULSE).min(1.);
+        // Harder hits sound deeper.
+        let speed = vary_pitch(1.3 - 0.5 * loudness);
+        commands.spawn(spatial_sound_effect(
+            handle.clone(),
+            position,
+            Volume::Linear(loudness),
+            speed,
+        ));
+    }
+}
+
+fn dash_sounds(
+    mut commands: Commands,
+    mut dash_started: MessageReader<DashStarted>,
+    players: Query<&GlobalTransform>,
+    sfx_assets: Res<SfxAssets>,
+) {
+    for event in dash_started.read() {
+        let Some(handle) = sfx_assets.impacts.choose(&mut rand::rng()) else {
+            return;
+        };
+        let Ok(transform) = players.get(event.player) else {
+            continue;
+        };
+        commands.spawn(spatial_sound_effect(
+            handle.clone(),
+            transform.translation().truncate(),
+            Volume::Linear(0.6),
+            vary_pitch(1.8),
+        ));
+    }
+}
+
+fn guard_sounds(
+    mut commands: Commands,
+    mut guard_raised: MessageReader<GuardRaised>,
+    guards: Query<&Guard>,
+    broken: Query<(), Added<Stunned>>,
+    sfx_assets: Res<SfxAssets>,
+) {
+    for event in guard_raised.read() {
+        // A weakened guard sounds duller.
+        let meter = guards.get(event.player).map_or(1., |guard| guard.meter);
+        commands.spawn(varied_sound_effect(
+            sfx_assets.guard.clone(),
+            Volume::Linear(0.7),
+            vary_pitch(0.6 + 0.4 * meter),
+        ));
+    }
+    // Guards only stun by breaking.
+    for () in &broken {
+        commands.spawn(varied_sound_effect(
+            sfx_assets.guard.clone(),
+            Volume::Linear(1.),
+            vary_pitch(0.4),
+        ));
+    }
+}
+
+fn elimination_sounds(
+    mut commands: Commands,
+    mut eliminated: MessageReader<Eliminated>,
+    sfx_assets: Res<SfxAssets>,
+) {
+    for _ in eliminated.read() {
+        commands.spawn(sound_effect(sfx_assets.elimination.clone()));
+    }
+}
@@ -0,0 +1,122 @@
+//! Statistics about how every player did over a match, for the results screen.
+
+use bevy::prelude::*;
+
+use crate::{
+    AppSystems, PausableSystems,
+    duel::{
+        arena::Eliminated,
+        dash::DashStarted,
+        match_state::MatchState,
+        player::{MatchConfig, PlayerID},
+        weapon::Hit,
+    },
+    screens::Screen,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<MatchStats>();
+    app.add_systems(OnEnter(Screen::Gameplay), reset_stats);
+    app.add_systems(OnEnter(MatchState::Countdown), forget_last_hits);
+    app.add_systems(
+        Update,
+        (
+            count_hits,
+            count_dashes,
+            // Hits can eliminate in the same frame, so credit them first.
+            count_ring_outs.after(count_hits),
+            count_time_alive.run_if(in_state(MatchState::Fighting)),
+        )
+            .in_set(AppSystems::Update)
+            .in_set(PausableSystems)
+            .run_if(in_state(Screen::Gameplay)),
+    );
+}
+
+/// Stats of every player in the current or most recent match.
+#[derive(Resource, Debug, Default)]
+pub struct MatchStats {
+    /// Indexed by [`PlayerID`].
+    pub players: Vec<PlayerStats>,
+    /// Who last hit each player this round, indexed by [`PlayerID`].
+    last_hit_by: Vec<Option<PlayerID>>,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct PlayerStats {
+    pub hits_landed: u32,
+    /// Damage percent dealt to other players.
+    pub damage_dealt: f32,
+    /// Other players eliminated after last being hit by this player.
+    pub ring_outs: u32,
+    pub dashes_used: u32,
+    /// Seconds spent fighting before being eliminated, over all rounds.
+    pub time_alive: f32,
+}
+
+fn reset_stats(mut stats: ResMut<MatchStats>, config: Res<MatchConfig>) {
+    *stats = MatchStats {
+        players: vec![PlayerStats::default(); config.players.len()],
+        last_hit_by: vec![None; config.players.len()],
+    };
+}
+
+fn forget_last_hits(mut stats: ResMut<MatchStats>) {
+    stats.last_hit_by.fill(None);
+}
+
+fn count_hits(
+    mut hits: MessageReader<Hit>,
+    players: Query<&PlayerID>,
+    mut stats: ResMut<MatchStats>,
+) {
+    for hit in hits.read() {
+        let (Ok(&attacker), Ok(&PlayerID(victim))) =
+            (players.get(hit.attacker), players.get(hit.victim))
+        else {
+            continue;
+        };
+        if let Some(player) = stats.players.get_mut(attacker.0) {
+            player.hits_landed += 1;
+            player.damage_dealt += hit.damage;
+        }
+        if let Some(last_hit_by) = stats.last_hit_by.get_mut(victim) {
+            *last_hit_by = Some(attacker);
+        }
+    }
+}
+
+fn count_dashes(
+    mut dash_started: MessageReader<DashStarted>,
+    players: Query<&PlayerID>,
+    mut stats: ResMut<MatchStats>,
+) {
+    for event in dash_started.read() {
+        if let Ok(&PlayerID(index)) = players.get(event.player)
+            && let Some(player) = stats.players.get_mut(index)
+        {
+            player.dashes_used += 1;
+        }
+    }
+}
+
+fn count_ring_outs(mut eliminated: MessageReader<Eliminated>, mut stats: ResMut<MatchStats>) {
+    for event in eliminated.read() {
+        // Taking the last hit means a player can only be credited for a knockout once.
+        let Some(PlayerID(attacker)) = stats.last_hit_by.get_mut(event.id.0).and_then(Option::take)
+        else {
+            continue;
+        };
+        if let Some(player) = stats.players.get_mut(attacker) {
+            player.ring_outs += 1;
+        }
+    }
+}
+
+fn count_time_alive(time: Res<Time>, players: Query<&PlayerID>, mut stats: ResMut<MatchStats>) {
+    for &PlayerID(index) in &players {
+        if let Some(player) = stats.players.get_mut(index) {
+            player.time_alive += time.delta_secs();
+        }
+    }
+}
@@ -0,0 +1,155 @@
+//! Physics and gameplay constants, loaded from `assets/tuning/default.tuning.ron`.
+//!
+//! In native dev builds the file is hot-reloaded, and changes are applied to live entities.
+
+use avian2d::prelude::*;
+use bevy::prelude::*;
+use serde::Deserialize;
+
+use crate::asset_tracking::{LoadResource, RonAssetLoader};
+#[cfg(feature = "dev_native")]
+use crate::duel::{
+    arena::ArenaGeometry,
+    player::{PlayerID, PlayerParts, Speed},
+    power_up::PowerUpModifiers,
+    weapon::{ChainJoint, FlailHead},
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_asset::<Tuning>();
+    app.register_asset_loader(RonAssetLoader::<Tuning>::new(&["tuning.ron"]));
+    app.load_resource::<TuningAssets>();
+
+    app.add_systems(Update, sync_tuning.run_if(resource_exists::<TuningAssets>));
+    #[cfg(feature = "dev_native")]
+    app.add_systems(
+        Update,
+        apply_tuning
+            .after(sync_tuning)
+            .run_if(resource_exists_and_changed::<Tuning>),
+    );
+}
+
+/// Constants that make duels feel the way they do.
+///
+/// Only exists as a resource once the tuning file has been loaded.
+#[derive(Resource, Asset, Clone, Debug, Deserialize, Reflect)]
+#[reflect(Resource)]
+pub struct Tuning {
+    /// Downward acceleration applied to every rigid body.
+    pub gravity: f32,
+    /// Friction of every body in a duel, including the arena.
+    pub friction: f32,
+    /// Restitution of every dynamic body in a duel.
+    pub restitution: f32,
+    pub player: PlayerTuning,
+    pub weapon: WeaponTuning,
+}
+
+#[derive(Clone, Debug, Deserialize, Reflect)]
+pub struct PlayerTuning {
+    pub radius: f32,
+    /// Movement force at full input.
+    pub speed: f32,
+    pub max_speed: f32,
+}
+
+#[derive(Clone, Debug, Deserialize, Reflect)]
+pub struct WeaponTuning {
+    pub flail_head_radius: f32,
+    /// Maximum length of a single link of a flail's chain.
+    pub chain_link_length: f32,
+    /// Hits closing in slower than this are just bumps.
+    pub min_hit_speed: f32,
+    /// Damage percent dealt per unit of hit strength.
+    pub damage_per_speed: f32,
+    /// Knockback speed per unit of hit strength, before damage scaling.
+    pub knockback_per_speed: f32,
+    /// How hard weapons are pulled in the direction their player aims at full input.
+    pub aim_acceleration: f32,
+}
+
+#[derive(Resource, Asset, Clone, Reflect)]
+#[reflect(Resource)]
+struct TuningAssets {
+    #[dependency]
+    tuning: Handle<Tuning>,
+}
+
+impl FromWorld for TuningAssets {
+    fn from_world(world: &mut World) -> Self {
+        let assets = world.resource::<AssetServer>();
+        Self {
+            tuning: assets.load("tuning/default.tuning.ron"),
+        }
+    }
+}
+
+/// Keep the [`Tuning`] resource in sync with the loaded tuning file.
+fn sync_tuning(
+    mut commands: Commands,
+    tuning_assets: Res<TuningAssets>,
+    tunings: Res<Assets<Tuning>>,
+    mut asset_events: MessageReader<AssetEvent<Tuning>>,
+    mut gravity: ResMut<Gravity>,
+) {
+    let modified = asset_events
+        .read()
+        .any(|event| event.is_modified(&tuning_assets.tuning));
+    if !tuning_assets.is_added() && !modified {
+        return;
+    }
+    let Some(tuning) = tunings.get(&tuning_assets.tuning) else {
+        return;
+    };
+    gravity.0 = Vec2::NEG_Y * tuning.gravity;
+    commands.insert_resource(tuning.clone());
+}
+
+/// Apply a reloaded tuning file to everything already spawned.
+#[cfg(feature = "dev_native")]
+fn apply_tuning(
+    tuning: Res<Tuning>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut players: Query<
+        (
+            &mut Speed,
+            &mut MaxLinearSpeed,
+            &mut Collider,
+            &Mesh2d,
+            &PowerUpModifiers,
+            &PlayerParts,
+        ),
+        With<PlayerID>,
+    >,
+    mut flail_heads: Query<(&mut Collider, &Mesh2d), (With<FlailHead>, Without<PlayerID>)>,
+    mut chain_joints: Query<&mut DistanceJoint, With<ChainJoint>>,
+    mut arena_geometry: Query<&mut Friction, With<ArenaGeometry>>,
+    mut bodies: Query<(&mut Friction, &mut Restitution), Without<ArenaGeometry>>,
+) {
+    let mut resize = |collider: &mut Collider, mesh: &Mesh2d, radius: f32| {
+        *collider = Collider::circle(radius);
+        let _ = meshes.insert(&mesh.0, Circle::new(radius).into());
+    };
+    // Keep power-ups in effect on top of the new values.
+    for (mut speed, mut max_speed, mut collider, mesh, modifiers, parts) in &mut players {
+        speed.0 = tuning.player.speed * modifiers.speed;
+        max_speed.0 = tuning.player.max_speed * modifiers.speed;
+        resize(&mut collider, mesh, tuning.player.radius);
+        for part in parts.iter() {
+            if let Ok(mut joint) = chain_joints.get_mut(part) {
+                joint.limits.max = tuning.weapon.chain_link_length * modifiers.reach;
+            }
+        }
+    }
+    for (mut collider, mesh) in &mut flail_heads {
+        resize(&mut collider, mesh, tuning.weapon.flail_head_radius);
+    }
+    for mut friction in &mut arena_geometry {
+        *friction = Friction::new(tuning.friction);
+    }
+    for (mut friction, mut restitution) in &mut bodies {
+        *friction = Friction::new(tuning.friction);
+        *restitution = Restitution::new(tuning.restitution);
+    }
+}
@@ -11,37 +11,375 @@
 use avian2d::prelude::*;
 use bevy::prelude::*;
 
-use crate::duel::dynamic_obj;
+use crate::{
+    duel::{
+        dash::Dash,
+        dynamic_obj,
+        guard::{GUARD_DAMAGE_MULTIPLIER, GUARD_KNOCKBACK_MULTIPLIER, Guard, PARRY_REPEL_SPEED},
+        player::{Damage, PlayerID, PlayerPart},
+        projectile::Launcher,
+        tuning::Tuning,
+    },
+    screens::Screen,
+};
 
-pub(super) fn plugin(app: &mut App) {}
+pub(super) fn plugin(app: &mut App) {
+    app.add_message::<Hit>();
+    app.add_systems(Update, weapon_hits.run_if(in_state(Screen::Gameplay)));
+}
+
+/// Written when a weapon hurts a player. Parried hits don't count.
+#[derive(Message, Debug, Clone, Copy)]
+pub struct Hit {
+    pub attacker: Entity,
+    pub victim: Entity,
+    /// Damage percent dealt.
+    pub damage: f32,
+}
 
+/// Marks a collider that hurts opposing players when it hits them.
+///
+/// The rigid body the collider is attached to must be a [`PlayerPart`] of its wielder.
 #[derive(Component)]
+#[require(CollisionEventsEnabled)]
 pub struct Weapon;
 
-fn weapon_joint(player: Entity, weapon: Entity) -> impl Bundle {
-    DistanceJoint::new(weapon, player)
-        .with_limits(0.001, 200.)
-        .with_compliance(0.0)
+/// The rigid body a player swings around, such as a flail's head or a sword.
+#[derive(Component)]
+pub struct WeaponBody;
+
+/// The head of a flail.
+#[derive(Component)]
+pub struct FlailHead;
+
+/// One of the joints making up a flail's chain.
+#[derive(Component)]
+pub struct ChainJoint;
+
+/// The kinds of weapon a player can be spawned with.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Reflect)]
+pub enum WeaponKind {
+    /// A spiked ball at the end of a chain.
+    #[default]
+    Flail,
+    /// A rigid blade that pivots around the player.
+    Sword,
+    /// A heavy head on a short handle, slow to swing but hits hard.
+    Hammer,
+    /// A barrel that fires projectiles instead of hitting things itself.
+    Blaster,
 }
 
-fn weapon(commands: &mut Commands, player: Entity) -> Entity {
-    let weapon = commands.spawn(Weapon).id();
-    commands.spawn(weapon_joint(player, weapon));
-    weapon
+impl WeaponKind {
+    /// Every kind of weapon, in the order they're offered to players.
+    pub const ALL: [Self; 4] = [Self::Flail, Self::Sword, Self::Hammer, Self::Blaster];
 }
 
+const CHAIN_LINKS: usize = 4;
+const CHAIN_LINK_RADIUS: f32 = 4.;
+const CHAIN_COLOR: Color = Color::srgb(0.6, 0.6, 0.6);
+
+/// Distance from the player's center to the sword's pivot on the blade.
+const SWORD_REACH: f32 = 55.;
+const SWORD_RADIUS: f32 = 5.;
+const SWORD_LENGTH: f32 = 60.;
+
+const HAMMER_REACH: f32 = 50.;
+const HAMMER_HANDLE_LENGTH: f32 = 50.;
+const HAMMER_HEAD_SIZE: Vec2 = Vec2::new(44., 26.);
+const HAMMER_HEAD_DENSITY: f32 = 6.;
+const HAMMER_ANGULAR_DAMPING: f32 = 1.5;
+
+const BLASTER_REACH: f32 = 35.;
+const BLASTER_RADIUS: f32 = 7.;
+const BLASTER_LENGTH: f32 = 24.;
+
 pub(crate) fn spawn_weapon(
     commands: &mut Commands,
     meshes: &mut ResMut<Assets<Mesh>>,
     materials: &mut ResMut<Assets<ColorMaterial>>,
+    tuning: &Tuning,
+    kind: WeaponKind,
     player: Entity,
+    position: Vec2,
+    color: Color,
+) {
+    let material = materials.add(ColorMaterial::from(color.darker(0.2)));
+    match kind {
+        WeaponKind::Flail => {
+            let link_length = tuning.weapon.chain_link_length;
+            let head_radius = tuning.weapon.flail_head_radius;
+            let chain_material = materials.add(CHAIN_COLOR);
+            let link_mesh = meshes.add(Circle::new(CHAIN_LINK_RADIUS));
+            let mut previous = player;
+            for i in 1..=CHAIN_LINKS {
+                let link = commands
+                    .spawn((
+                        Name::new("Flail Chain Link"),
+                        PlayerPart(player),
+                        chain_link(),
+                        Mesh2d(link_mesh.clone()),
+                        MeshMaterial2d(chain_material.clone()),
+                        Transform::from_translation(
+                            (position + Vec2::X * link_length * i as f32).extend(0.),
+                        ),
+                    ))
+                    .id();
+                commands.spawn((chain_joint(previous, link, link_length), PlayerPart(player)));
+                previous = link;
+            }
+
+            let head_position = position + Vec2::X * link_length * (CHAIN_LINKS + 1) as f32;
+            let head = commands
+                .spawn((
+                    Name::new("Flail Head"),
+                    Weapon,
+                    WeaponBody,
+                    FlailHead,
+                    PlayerPart(player),
+                    dynamic_obj(head_radius, tuning),
+                    Mesh2d(meshes.add(Circle::new(head_radius))),
+                    MeshMaterial2d(material),
+                    Transform::from_translation(head_position.extend(0.)),
+                ))
+                .id();
+            commands.spawn((chain_joint(previous, head, link_length), PlayerPart(player)));
+        }
+        WeaponKind::Sword => {
+            let sword = commands
+                .spawn((
+                    Name::new("Sword"),
+                    WeaponBody,
+                    PlayerPart(player),
+                    RigidBody::Dynamic,
+                    Collider::capsule(SWORD_RADIUS, SWORD_LENGTH),
+                    Friction::new(tuning.friction),
+                    Restitution::new(tuning.restitution),
+                    TransformExtrapolation,
+                    TransformHermiteEasing,
+                    DespawnOnExit(Screen::Gameplay),
+                    Mesh2d(meshes.add(Capsule2d::new(SWORD_RADIUS, SWORD_LENGTH))),
+                    MeshMaterial2d(material),
+                    Transform::from_translation((position + Vec2::Y * SWORD_REACH).extend(0.)),
+                    children![(
+                        Name::new("Sword Blade"),
+                        Weapon,
+                        Sensor,
+                        // Slightly larger than the sword so that grazing hits still count.
+                        Collider::capsule(SWORD_RADIUS + 4., SWORD_LENGTH * 0.8),
+                        Transform::from_xyz(0., SWORD_LENGTH * 0.1, 0.),
+                    )],
+                ))
+                .id();
+            commands.spawn((pivot_joint(player, sword, SWORD_REACH), PlayerPart(player)));
+        }
+        WeaponKind::Hammer => {
+            let hammer = commands
+                .spawn((
+                    Name::new("Hammer"),
+                    WeaponBody,
+                    PlayerPart(player),
+                    RigidBody::Dynamic,
+                    Collider::capsule(CHAIN_LINK_RADIUS, HAMMER_HANDLE_LENGTH),
+                    AngularDamping(HAMMER_ANGULAR_DAMPING),
+                    TransformExtrapolation,
+                    TransformHermiteEasing,
+                    DespawnOnExit(Screen::Gameplay),
+                    Mesh2d(meshes.add(Capsule2d::new(CHAIN_LINK_RADIUS, HAMMER_HANDLE_LENGTH))),
+                    MeshMaterial2d(materials.add(CHAIN_COLOR)),
+                    Transform::from_translation((position + Vec2::Y * HAMMER_REACH).extend(0.)),
+                    children![(
+                        Name::new("Hammer Head"),
+                        Weapon,
+                        Collider::rectangle(HAMMER_HEAD_SIZE.x, HAMMER_HEAD_SIZE.y),
+                        ColliderDensity(HAMMER_HEAD_DENSITY),
+                        Friction::new(tuning.friction),
+                        Restitution::new(tuning.restitution),
+                        Mesh2d(meshes.add(Rectangle::from_size(HAMMER_HEAD_SIZE))),
+                        MeshMaterial2d(material),
+                        Transform::from_xyz(0., HAMMER_HANDLE_LENGTH / 2., 0.),
+                    )],
+                ))
+                .id();
+            commands.spawn((
+                pivot_joint(player, hammer, HAMMER_REACH),
+                PlayerPart(player),
+            ));
+        }
+        WeaponKind::Blaster => {
+            let blaster = commands
+                .spawn((
+                    Name::new("Blaster"),
+                    WeaponBody,
+                    PlayerPart(player),
+                    Launcher::new(BLASTER_LENGTH / 2. + BLASTER_RADIUS),
+                    RigidBody::Dynamic,
+                    Collider::capsule(BLASTER_RADIUS, BLASTER_LENGTH),
+                    Friction::new(tuning.friction),
+                    Restitution::new(tuning.restitution),
+                    TransformExtrapolation,
+                    TransformHermiteEasing,
+                    DespawnOnExit(Screen::Gameplay),
+                    Mesh2d(meshes.add(Capsule2d::new(BLASTER_RADIUS, BLASTER_LENGTH))),
+                    MeshMaterial2d(material),
+                    Transform::from_translation((position + Vec2::Y * BLASTER_REACH).extend(0.)),
+                ))
+                .id();
+            commands.spawn((
+                pivot_joint(player, blaster, BLASTER_REACH),
+                PlayerPart(player),
+            ));
+        }
+    }
+}
+
+/// A light, collider-less body that makes up a flail's chain.
+fn chain_link() -> impl Bundle {
+    (
+        RigidBody::Dynamic,
+        Mass(20.),
+        AngularInertia(1.),
+        TransformExtrapolation,
+        TransformHermiteEasing,
+        DespawnOnExit(Screen::Gameplay),
+    )
+}
+
+/// A slack rope between two neighbouring parts of a flail.
+fn chain_joint(body1: Entity, body2: Entity, length: f32) -> impl Bundle {
+    (
+        ChainJoint,
+        DistanceJoint::new(body1, body2)
+            .with_limits(0.001, length)
+            .with_compliance(0.0),
+    )
+}
+
+/// Lets a rigid weapon pointing along its local y axis swing freely around the player's
+/// center, `reach` away from it.
+fn pivot_joint(player: Entity, weapon: Entity, reach: f32) -> impl Bundle {
+    (
+        RevoluteJoint::new(player, weapon).with_local_anchor2(Vec2::NEG_Y * reach),
+        JointCollisionDisabled,
+    )
+}
+
+/// Weapons heavier than this hit harder, lighter ones hit softer.
+const REFERENCE_WEAPON_MASS: f32 = 1250.;
+
+/// Knockback speed of a hit with the given strength on a player with `damage` percent.
+///
+/// The more hurt a player is, the further they fly.
+fn knockback(tuning: &Tuning, strength: f32, damage: f32) -> f32 {
+    strength * tuning.weapon.knockback_per_speed * (1. + damage / 100.)
+}
+
+fn weapon_hits(
+    mut collision_starts: MessageReader<CollisionStart>,
+    weapons: Query<&GlobalTransform, With<Weapon>>,
+    mut weapon_bodies: Query<
+        (
+            &PlayerPart,
+            &Position,
+            &mut LinearVelocity,
+            &AngularVelocity,
+            &ComputedMass,
+        ),
+        Without<PlayerID>,
+    >,
+    mut victims: Query<
+        (
+            &Position,
+            &mut LinearVelocity,
+            &mut Damage,
+            &mut Guard,
+            &Dash,
+            &ComputedMass,
+        ),
+        With<PlayerID>,
+    >,
+    collisions: Collisions,
+    tuning: Res<Tuning>,
+    mut hits: MessageWriter<Hit>,
 ) {
-    let weapon_entity = weapon(commands, player.entity());
-    commands.entity(weapon_entity).insert((
-        Mesh2d(meshes.add(Mesh::from(Circle::new(20.)))),
-        MeshMaterial2d::from(materials.add(ColorMaterial::from(Color::srgb(0.8, 0.2, 0.2)))),
-        dynamic_obj(20.),
-        Name::new("Player Weapon"),
-        Transform::from_xyz(50., 0., 0.),
-    ));
+    for event in collision_starts.read() {
+        let pairs = [
+            (event.collider1, event.body1, event.body2),
+            (event.collider2, event.body2, event.body1),
+        ];
+        for (collider, weapon_body, other_body) in pairs {
+            let (Some(weapon_body), Some(victim)) = (weapon_body, other_body) else {
+                continue;
+            };
+            let Ok(weapon_transform) = weapons.get(collider) else {
+                continue;
+            };
+            let Ok((&PlayerPart(attacker), body_position, mut linear, angular, mass)) =
+                weapon_bodies.get_mut(weapon_body)
+            else {
+                continue;
+            };
+            if attacker == victim {
+                continue;
+            }
+            let Ok((
+                victim_position,
+                mut victim_velocity,
+                mut damage,
+                mut guard,
+                dash,
+                victim_mass,
+            )) = victims.get_mut(victim)
+            else {
+                continue;
+            };
+            if dash.is_intangible() {
+                continue;
+            }
+
+            // Swinging weapons move faster at their tip than at their center of rotation.
+            let weapon_position = weapon_transform.translation().truncate();
+            let weapon_velocity = linear.0 + angular.0 * (weapon_position - body_position.0).perp();
+
+            let direction = (victim_position.0 - weapon_position).normalize_or_zero();
+            let impulse = collisions
+                .get(event.collider1, event.collider2)
+                .map_or(0., |contacts| contacts.total_normal_impulse_magnitude());
+            let closing_speed = if impulse > 0. {
+                // Solid weapons have already bounced off by now, so work back from the impulse
+                // the solver applied, which is (1 + restitution) * closing speed * reduced mass.
+                let reduced_mass =
+                    mass.value() * victim_mass.value() / (mass.value() + victim_mass.value());
+                impulse / ((1. + tuning.restitution) * reduced_mass)
+            } else {
+                // Sensors don't collide, so velocities are still those at the moment of the hit.
+                (weapon_velocity - victim_velocity.0).dot(direction)
+            };
+            if closing_speed < tuning.weapon.min_hit_speed {
+                continue;
+            }
+            let strength = closing_speed * (mass.value() / REFERENCE_WEAPON_MASS).sqrt();
+
+            if guard.is_parrying() {
+                linear.0 = -direction * PARRY_REPEL_SPEED;
+                continue;
+            }
+
+            let (damage_multiplier, knockback_multiplier) = if guard.active {
+                guard.absorb(strength);
+                (GUARD_DAMAGE_MULTIPLIER, GUARD_KNOCKBACK_MULTIPLIER)
+            } else {
+                (1., 1.)
+            };
+            let dealt = strength * tuning.weapon.damage_per_speed * damage_multiplier;
+            damage.0 += dealt;
+            hits.write(Hit {
+                attacker,
+                victim,
+                damage: dealt,
+            });
+            victim_velocity.0 +=
+                direction * knockback(&tuning, strength, damage.0) * knockback_multiplier;
+        }
+    }
 }
@@ -9,7 +9,10 @@ mod audio;
 mod dev_tools;
 mod duel;
 mod menus;
+mod music;
+mod persistence;
 mod screens;
+mod settings;
 mod theme;
 
 use bevy::{asset::AssetMetaCheck, prelude::*};
@@ -52,7 +55,9 @@ impl Plugin for AppPlugin {
             #[cfg(feature = "dev")]
             dev_tools::plugin,
             menus::plugin,
+            music::plugin,
             screens::plugin,
+            settings::plugin,
             theme::plugin,
         ));
 
@@ -98,5 +103,9 @@ struct Pause(pub bool);
 struct PausableSystems;
 
 fn spawn_camera(mut commands: Commands) {
-    commands.spawn((Name::new("Camera"), Camera2d));
+    commands.spawn((
+        Name::new("Camera"),
+        Camera2d,
+        SpatialListener::new(audio::LISTENER_EAR_GAP),
+    ));
 }
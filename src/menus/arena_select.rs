@@ -0,0 +1,131 @@
+//! The arena select menu, opened by pressing "Play" in the main menu.
+
+use bevy::{input::common_conditions::input_just_pressed, prelude::*};
+
+use crate::{
+    duel::{
+        arena::{Arena, ArenaAssets},
+        player::MatchConfig,
+    },
+    menus::Menu,
+    screens::Screen,
+    theme::widget,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(OnEnter(Menu::ArenaSelect), spawn_arena_select_menu);
+    app.add_systems(
+        Update,
+        (
+            // Arenas may still be loading when the menu is opened.
+            spawn_arena_select_menu.run_if(resource_added::<ArenaAssets>),
+            go_back.run_if(input_just_pressed(KeyCode::Escape)),
+        )
+            .run_if(in_state(Menu::ArenaSelect)),
+    );
+}
+
+const THUMBNAIL_WIDTH: u32 = 240;
+
+#[derive(Component)]
+struct ArenaSelectMenu;
+
+fn spawn_arena_select_menu(
+    mut commands: Commands,
+    menus: Query<Entity, With<ArenaSelectMenu>>,
+    arena_assets: Option<Res<ArenaAssets>>,
+    arenas: Res<Assets<Arena>>,
+    mut images: ResMut<Assets<Image>>,
+) {
+    for menu in &menus {
+        commands.entity(menu).despawn();
+    }
+
+    let root = (
+        widget::ui_root("Arena Select Menu"),
+        ArenaSelectMenu,
+        GlobalZIndex(2),
+        DespawnOnExit(Menu::ArenaSelect),
+    );
+    let Some(arena_assets) = arena_assets else {
+        commands.spawn((
+            root,
+            children![
+                widget::header("Choose an Arena"),
+                widget::label("Loading arenas..."),
+                widget::button("Back", go_back_on_click),
+            ],
+        ));
+        return;
+    };
+
+    let cards = arena_assets
+        .arenas
+        .iter()
+        .enumerate()
+        .filter_map(|(index, handle)| {
+            let arena = arenas.get(handle)?;
+            let thumbnail = arena.thumbnail(THUMBNAIL_WIDTH);
+            let size = thumbnail.size_f32();
+            Some(widget::image_button(
+                images.add(thumbnail),
+                size,
+                arena.name.clone(),
+                choose_arena(index),
+            ))
+        })
+        .collect::<Vec<_>>();
+    commands.spawn((
+        root,
+        children![
+            widget::header("Choose an Arena"),
+            (
+                Name::new("Arena List"),
+                Node {
+                    flex_wrap: FlexWrap::Wrap,
+                    justify_content: JustifyContent::Center,
+                    column_gap: px(20),
+                    row_gap: px(20),
+                    max_width: percent(90),
+                    ..default()
+                },
+                Children::spawn(SpawnIter(cards.into_iter())),
+            ),
+            widget::button("Back", go_back_on_click),
+        ],
+    ));
+}
+
+/// Fight in the arena at `index` into [`ArenaAssets::arenas`], once players have joined in
+/// the lobby. After a match, the same players go straight into the new arena instead.
+fn choose_arena(
+    index: usize,
+) -> impl Fn(On<Pointer<Click>>, ResMut<MatchConfig>, Res<State<Screen>>, ResMut<NextState<Screen>>)
+{
+    move |_, mut config, screen, mut next_screen| {
+        config.arena = index;
+        next_screen.set(if *screen.get() == Screen::Results {
+            Screen::Loading
+        } else {
+            Screen::Lobby
+        });
+    }
+}
+
+fn go_back_on_click(
+    _: On<Pointer<Click>>,
+    screen: Res<State<Screen>>,
+    next_menu: ResMut<NextState<Menu>>,
+) {
+    go_back(screen, next_menu);
+}
+
+/// Return to the main menu, or to the results of the last match if that's where the menu was
+/// opened from.
+fn go_back(screen: Res<State<Screen>>, mut next_menu: ResMut<NextState<Menu>>) {
+    next_menu.set(if *screen.get() == Screen::Results {
+        Menu::None
+    } else {
+        Menu::Main
+    });
+}
@@ -1,5 +1,6 @@
 //! The game's menus and transitions between them.
 
+mod arena_select;
 mod credits;
 mod main;
 mod pause;
@@ -7,10 +8,13 @@ mod settings;
 
 use bevy::prelude::*;
 
+pub use settings::not_rebinding;
+
 pub(super) fn plugin(app: &mut App) {
     app.init_state::<Menu>();
 
     app.add_plugins((
+        arena_select::plugin,
         credits::plugin,
         main::plugin,
         settings::plugin,
@@ -23,6 +27,7 @@ pub enum Menu {
     #[default]
     None,
     Main,
+    ArenaSelect,
     Credits,
     Settings,
     Pause,
@@ -1,106 +1,504 @@
 //! The settings menu.
 //!
-//! Additional settings and accessibility options should go here.
+//! Additional settings and accessibility options should go here, backed by [`Settings`].
 
-use bevy::{audio::Volume, input::common_conditions::input_just_pressed, prelude::*};
+use bevy::{
+    ecs::system::IntoObserverSystem, input::common_conditions::input_just_pressed, prelude::*,
+};
 
-use crate::{menus::Menu, screens::Screen, theme::prelude::*};
+use crate::{
+    audio::CategoryVolume,
+    duel::bindings::{Binding, Bindings, Control, ControlProfile},
+    menus::Menu,
+    screens::Screen,
+    settings::Settings,
+    theme::prelude::*,
+};
 
 pub(super) fn plugin(app: &mut App) {
-    app.add_systems(OnEnter(Menu::Settings), spawn_settings_menu);
+    app.init_resource::<SettingsMenuState>();
+    app.add_systems(OnEnter(Menu::Settings), reset_menu_state);
+    app.add_systems(OnExit(Menu::Settings), save_settings);
     app.add_systems(
         Update,
-        go_back.run_if(in_state(Menu::Settings).and(input_just_pressed(KeyCode::Escape))),
+        spawn_settings_menu.run_if(
+            in_state(Menu::Settings)
+                .and(resource_changed::<SettingsMenuState>.or(resource_changed::<Settings>)),
+        ),
     );
-
     app.add_systems(
         Update,
-        update_global_volume_label.run_if(in_state(Menu::Settings)),
+        (
+            go_back.run_if(input_just_pressed(KeyCode::Escape).and(not_rebinding)),
+            capture_binding,
+        )
+            .chain()
+            .run_if(in_state(Menu::Settings)),
     );
 }
 
-fn spawn_settings_menu(mut commands: Commands) {
-    commands.spawn((
-        widget::ui_root("Settings Menu"),
-        GlobalZIndex(2),
-        DespawnOnExit(Menu::Settings),
-        children![
-            widget::header("Settings"),
-            settings_grid(),
-            widget::button("Back", go_back_on_click),
-        ],
-    ));
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+enum SettingsTab {
+    #[default]
+    Audio,
+    Display,
+    Controls,
+}
+
+impl SettingsTab {
+    const ALL: [Self; 3] = [Self::Audio, Self::Display, Self::Controls];
+
+    fn label(self) -> &'static str {
+        match self {
+            Self::Audio => "Audio",
+            Self::Display => "Display",
+            Self::Controls => "Controls",
+        }
+    }
+}
+
+/// State of the menu itself, as opposed to the [`Settings`] it edits.
+#[derive(Resource, Debug, Default)]
+pub struct SettingsMenuState {
+    tab: SettingsTab,
+    /// Index into [`ControlProfile::ALL`].
+    profile: usize,
+    /// The control waiting for a new key or button.
+    rebinding: Option<Control>,
+    message: String,
 }
 
-fn settings_grid() -> impl Bundle {
+impl SettingsMenuState {
+    fn profile(&self) -> ControlProfile {
+        ControlProfile::ALL[self.profile]
+    }
+}
+
+#[derive(Component)]
+struct SettingsMenu;
+
+fn reset_menu_state(mut commands: Commands) {
+    commands.insert_resource(SettingsMenuState::default());
+}
+
+fn save_settings(settings: Res<Settings>) {
+    settings.save();
+}
+
+/// Whether the settings menu is free, rather than waiting for a key to bind.
+pub fn not_rebinding(menu_state: Res<SettingsMenuState>) -> bool {
+    menu_state.rebinding.is_none()
+}
+
+fn spawn_settings_menu(
+    mut commands: Commands,
+    menu_state: Res<SettingsMenuState>,
+    settings: Res<Settings>,
+    menus: Query<Entity, With<SettingsMenu>>,
+) {
+    // The whole menu is rebuilt whenever anything on it changes.
+    for menu in &menus {
+        commands.entity(menu).despawn();
+    }
+    let tabs = SettingsTab::ALL.map(|tab| {
+        let text = if tab == menu_state.tab {
+            format!("[ {} ]", tab.label())
+        } else {
+            tab.label().to_string()
+        };
+        widget::button_medium(text, open_tab(tab))
+    });
+    commands
+        .spawn((
+            widget::ui_root("Settings Menu"),
+            SettingsMenu,
+            GlobalZIndex(2),
+            DespawnOnExit(Menu::Settings),
+            children![
+                widget::header("Settings"),
+                (
+                    Name::new("Tabs"),
+                    Node {
+                        column_gap: px(10),
+                        ..default()
+                    },
+                    Children::spawn(SpawnIter(tabs.into_iter())),
+                ),
+            ],
+        ))
+        .with_children(|parent| {
+            match menu_state.tab {
+                SettingsTab::Audio => parent.spawn(audio_grid(&settings)),
+                SettingsTab::Display => parent.spawn(display_grid(&settings)),
+                SettingsTab::Controls => parent.spawn(controls_section(&menu_state, &settings)),
+            };
+            parent.spawn(widget::button("Back", go_back_on_click));
+        });
+}
+
+fn open_tab(tab: SettingsTab) -> impl Fn(On<Pointer<Click>>, ResMut<SettingsMenuState>) {
+    move |_, mut menu_state| {
+        menu_state.tab = tab;
+        menu_state.rebinding = None;
+        menu_state.message.clear();
+    }
+}
+
+fn settings_grid(name: &'static str) -> impl Bundle {
     (
-        Name::new("Settings Grid"),
+        Name::new(name),
         Node {
             display: Display::Grid,
             row_gap: px(10),
             column_gap: px(30),
-            grid_template_columns: RepeatedGridTrack::px(2, 400.0),
+            align_items: AlignItems::Center,
+            grid_template_columns: vec![GridTrack::px(300.0), GridTrack::px(400.0)],
             ..default()
         },
+    )
+}
+
+fn audio_grid(settings: &Settings) -> impl Bundle {
+    (
+        settings_grid("Audio Grid"),
         children![
+            setting_label("Master Volume"),
+            stepper_widget(
+                "Global Volume Widget",
+                settings.volume,
+                step_volume(None, -0.1),
+                step_volume(None, 0.1),
+            ),
+            setting_label("Music"),
+            category_volume_widget(AudioCategory::Music, settings.music),
+            setting_label("Sound Effects"),
+            category_volume_widget(AudioCategory::SoundEffects, settings.sound_effects),
+            setting_label("Interface"),
+            category_volume_widget(AudioCategory::UiSounds, settings.ui_sounds),
+        ],
+    )
+}
+
+fn display_grid(settings: &Settings) -> impl Bundle {
+    let on_off = |on: bool| if on { "On" } else { "Off" };
+    (
+        settings_grid("Display Grid"),
+        children![
+            setting_label("Fullscreen"),
             (
-                widget::label("Master Volume"),
                 Node {
-                    justify_self: JustifySelf::End,
+                    justify_self: JustifySelf::Start,
                     ..default()
-                }
+                },
+                children![widget::button_medium(
+                    on_off(settings.display.fullscreen),
+                    toggle_fullscreen,
+                )],
+            ),
+            setting_label("VSync"),
+            (
+                Node {
+                    justify_self: JustifySelf::Start,
+                    ..default()
+                },
+                children![widget::button_medium(
+                    on_off(settings.display.vsync),
+                    toggle_vsync,
+                )],
+            ),
+            setting_label("UI Scale"),
+            stepper_widget(
+                "UI Scale Widget",
+                settings.accessibility.ui_scale,
+                step_ui_scale(-0.1),
+                step_ui_scale(0.1),
             ),
-            global_volume_widget(),
         ],
     )
 }
 
-fn global_volume_widget() -> impl Bundle {
+fn setting_label(text: &'static str) -> impl Bundle {
+    (
+        widget::label(text),
+        Node {
+            justify_self: JustifySelf::End,
+            ..default()
+        },
+    )
+}
+
+/// A percentage with buttons to lower and raise it.
+fn stepper_widget<M1, M2>(
+    name: &'static str,
+    value: f32,
+    lower: impl IntoObserverSystem<Pointer<Click>, (), M1>,
+    raise: impl IntoObserverSystem<Pointer<Click>, (), M2>,
+) -> impl Bundle {
+    let percent = 100.0 * value;
     (
-        Name::new("Global Volume Widget"),
+        Name::new(name),
         Node {
             justify_self: JustifySelf::Start,
+            align_items: AlignItems::Center,
             ..default()
         },
         children![
-            widget::button_small("-", lower_global_volume),
+            widget::button_small("-", lower),
             (
-                Name::new("Current Volume"),
+                Name::new("Current Value"),
                 Node {
                     padding: UiRect::horizontal(px(10)),
                     justify_content: JustifyContent::Center,
                     ..default()
                 },
-                children![(widget::label(""), GlobalVolumeLabel)],
+                children![widget::label(format!("{percent:3.0}%"))],
+            ),
+            widget::button_small("+", raise),
+        ],
+    )
+}
+
+fn category_volume_widget(category: AudioCategory, volume: CategoryVolume) -> impl Bundle {
+    (
+        Name::new("Category Volume Widget"),
+        Node {
+            justify_self: JustifySelf::Start,
+            align_items: AlignItems::Center,
+            column_gap: px(16),
+            ..default()
+        },
+        children![
+            stepper_widget(
+                "Volume Stepper",
+                volume.volume,
+                step_volume(Some(category), -0.1),
+                step_volume(Some(category), 0.1),
+            ),
+            widget::button_medium(
+                if volume.muted { "Unmute" } else { "Mute" },
+                toggle_mute(category),
+            ),
+        ],
+    )
+}
+
+fn controls_section(menu_state: &SettingsMenuState, settings: &Settings) -> impl Bundle {
+    let bindings = &settings.bindings;
+    let profile = menu_state.profile();
+    let rows = Control::ALL
+        .into_iter()
+        .map(|control| {
+            let binding = if menu_state.rebinding == Some(control) {
+                if profile.uses_keyboard() {
+                    "Press a key...".to_string()
+                } else {
+                    "Press a button...".to_string()
+                }
+            } else {
+                bindings
+                    .profile(profile)
+                    .get(control)
+                    .map_or("Unbound".to_string(), |binding| binding.to_string())
+            };
+            control_row(control, binding)
+        })
+        .collect::<Vec<_>>();
+    let message = if menu_state.message.is_empty() {
+        "Click a control to rebind it. Aim with the right stick or the right mouse button."
+            .to_string()
+    } else {
+        menu_state.message.clone()
+    };
+
+    (
+        Name::new("Controls Section"),
+        Node {
+            flex_direction: FlexDirection::Column,
+            align_items: AlignItems::Center,
+            row_gap: px(10),
+            ..default()
+        },
+        children![
+            (
+                Name::new("Profile Picker"),
+                Node {
+                    align_items: AlignItems::Center,
+                    column_gap: px(10),
+                    ..default()
+                },
+                children![
+                    widget::button_small("<", previous_profile),
+                    widget::label(format!("Controls: {}", profile.label())),
+                    widget::button_small(">", next_profile),
+                    widget::button_medium("Reset Controls", reset_bindings),
+                ],
+            ),
+            (
+                Name::new("Controls Grid"),
+                Node {
+                    display: Display::Grid,
+                    row_gap: px(6),
+                    column_gap: px(30),
+                    grid_template_columns: RepeatedGridTrack::auto(2),
+                    ..default()
+                },
+                Children::spawn(SpawnIter(rows.into_iter())),
             ),
-            widget::button_small("+", raise_global_volume),
+            widget::label(message),
         ],
     )
 }
 
+fn control_row(control: Control, binding: String) -> impl Bundle {
+    (
+        Name::new("Control Row"),
+        Node {
+            align_items: AlignItems::Center,
+            column_gap: px(16),
+            ..default()
+        },
+        children![
+            (
+                widget::label(control.label()),
+                Node {
+                    width: px(140),
+                    ..default()
+                },
+                TextLayout::new_with_justify(Justify::Right),
+            ),
+            widget::button_medium(binding, start_rebinding(control)),
+        ],
+    )
+}
+
+fn previous_profile(_: On<Pointer<Click>>, mut menu_state: ResMut<SettingsMenuState>) {
+    let count = ControlProfile::ALL.len();
+    menu_state.profile = (menu_state.profile + count - 1) % count;
+    menu_state.rebinding = None;
+}
+
+fn next_profile(_: On<Pointer<Click>>, mut menu_state: ResMut<SettingsMenuState>) {
+    menu_state.profile = (menu_state.profile + 1) % ControlProfile::ALL.len();
+    menu_state.rebinding = None;
+}
+
+fn start_rebinding(control: Control) -> impl Fn(On<Pointer<Click>>, ResMut<SettingsMenuState>) {
+    move |_, mut menu_state| {
+        menu_state.rebinding = Some(control);
+        menu_state.message.clear();
+    }
+}
+
+fn reset_bindings(
+    _: On<Pointer<Click>>,
+    mut settings: ResMut<Settings>,
+    mut menu_state: ResMut<SettingsMenuState>,
+) {
+    settings.bindings = Bindings::default();
+    menu_state.rebinding = None;
+    menu_state.message = "Controls reset to their defaults.".to_string();
+}
+
+/// Bind the control waiting for input to the next key or gamepad button pressed.
+fn capture_binding(
+    keys: Res<ButtonInput<KeyCode>>,
+    gamepads: Query<&Gamepad>,
+    mut menu_state: ResMut<SettingsMenuState>,
+    mut settings: ResMut<Settings>,
+) {
+    let Some(control) = menu_state.rebinding else {
+        return;
+    };
+    if keys.just_pressed(KeyCode::Escape) {
+        menu_state.rebinding = None;
+        return;
+    }
+    let profile = menu_state.profile();
+    let binding = if profile.uses_keyboard() {
+        keys.get_just_pressed().next().copied().map(Binding::Key)
+    } else {
+        gamepads
+            .iter()
+            .find_map(|gamepad| gamepad.get_just_pressed().next().copied())
+            .map(Binding::Button)
+    };
+    let Some(binding) = binding else {
+        return;
+    };
+    if binding.is_reserved() {
+        menu_state.message =
+            format!("{binding} is used for pausing, menus or the lobby, pick another one.");
+        return;
+    }
+
+    menu_state.rebinding = None;
+    menu_state.message = match settings.bindings.rebind(profile, control, binding) {
+        Some((other_profile, other_control)) => format!(
+            "{binding} was already used for {} ({}), so they were swapped.",
+            other_control.label(),
+            other_profile.label(),
+        ),
+        None => String::new(),
+    };
+}
+
+#[derive(Clone, Copy, Debug)]
+enum AudioCategory {
+    Music,
+    SoundEffects,
+    UiSounds,
+}
+
+impl AudioCategory {
+    fn volume_mut(self, settings: &mut Settings) -> &mut CategoryVolume {
+        match self {
+            Self::Music => &mut settings.music,
+            Self::SoundEffects => &mut settings.sound_effects,
+            Self::UiSounds => &mut settings.ui_sounds,
+        }
+    }
+}
+
 const MIN_VOLUME: f32 = 0.0;
 const MAX_VOLUME: f32 = 3.0;
 
-fn lower_global_volume(_: On<Pointer<Click>>, mut global_volume: ResMut<GlobalVolume>) {
-    let linear = (global_volume.volume.to_linear() - 0.1).max(MIN_VOLUME);
-    global_volume.volume = Volume::Linear(linear);
+/// Change the volume of `category`, or the master volume if there's none.
+fn step_volume(
+    category: Option<AudioCategory>,
+    step: f32,
+) -> impl Fn(On<Pointer<Click>>, ResMut<Settings>) {
+    move |_, mut settings| {
+        let volume = match category {
+            Some(category) => &mut category.volume_mut(&mut settings).volume,
+            None => &mut settings.volume,
+        };
+        *volume = (*volume + step).clamp(MIN_VOLUME, MAX_VOLUME);
+    }
 }
 
-fn raise_global_volume(_: On<Pointer<Click>>, mut global_volume: ResMut<GlobalVolume>) {
-    let linear = (global_volume.volume.to_linear() + 0.1).min(MAX_VOLUME);
-    global_volume.volume = Volume::Linear(linear);
+fn toggle_mute(category: AudioCategory) -> impl Fn(On<Pointer<Click>>, ResMut<Settings>) {
+    move |_, mut settings| {
+        let volume = category.volume_mut(&mut settings);
+        volume.muted = !volume.muted;
+    }
 }
 
-#[derive(Component, Reflect)]
-#[reflect(Component)]
-struct GlobalVolumeLabel;
+fn toggle_fullscreen(_: On<Pointer<Click>>, mut settings: ResMut<Settings>) {
+    settings.display.fullscreen = !settings.display.fullscreen;
+}
 
-fn update_global_volume_label(
-    global_volume: Res<GlobalVolume>,
-    mut label: Single<&mut Text, With<GlobalVolumeLabel>>,
-) {
-    let percent = 100.0 * global_volume.volume.to_linear();
-    label.0 = format!("{percent:3.0}%");
+fn toggle_vsync(_: On<Pointer<Click>>, mut settings: ResMut<Settings>) {
+    settings.display.vsync = !settings.display.vsync;
+}
+
+const MIN_UI_SCALE: f32 = 0.5;
+const MAX_UI_SCALE: f32 = 2.0;
+
+fn step_ui_scale(step: f32) -> impl Fn(On<Pointer<Click>>, ResMut<Settings>) {
+    move |_, mut settings| {
+        let scale = &mut settings.accessibility.ui_scale;
+        *scale = (*scale + step).clamp(MIN_UI_SCALE, MAX_UI_SCALE);
+    }
 }
 
 fn go_back_on_click(
@@ -0,0 +1,161 @@
+//! Picks the music for whatever screen or menu is showing, crossfading between tracks.
+//!
+//! Music entities aren't tied to any screen, so they're never cut off by [`DespawnOnExit`].
+//! Instead, tracks that are no longer wanted fade out and despawn themselves.
+
+use bevy::prelude::*;
+
+use crate::{
+    Pause,
+    asset_tracking::LoadResource,
+    audio::{VolumeScale, music},
+    duel::{
+        match_state::{MatchScore, MatchState},
+        player::MatchConfig,
+    },
+    menus::Menu,
+    screens::Screen,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.load_resource::<MusicAssets>();
+    app.add_systems(
+        Update,
+        (
+            direct_music.run_if(resource_exists::<MusicAssets>),
+            fade_music,
+        )
+            .chain(),
+    );
+}
+
+#[derive(Resource, Asset, Clone, Reflect)]
+#[reflect(Resource)]
+struct MusicAssets {
+    #[dependency]
+    menu: Handle<AudioSource>,
+    #[dependency]
+    gameplay: Handle<AudioSource>,
+    #[dependency]
+    credits: Handle<AudioSource>,
+}
+
+impl FromWorld for MusicAssets {
+    fn from_world(world: &mut World) -> Self {
+        let assets = world.resource::<AssetServer>();
+        Self {
+            menu: assets.load("audio/music/Monkeys Spinning Monkeys.ogg"),
+            gameplay: assets.load("audio/music/Fluffing A Duck.ogg"),
+            credits: assets.load("audio/music/Monkeys Spinning Monkeys.ogg"),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Reflect)]
+enum Track {
+    Menu,
+    Gameplay,
+    Credits,
+}
+
+/// A playing track, fading towards `target` volume.
+#[derive(Component, Debug, Reflect)]
+#[reflect(Component)]
+struct MusicTrack {
+    track: Track,
+    target: f32,
+}
+
+/// How long it takes a track to fade all the way in or out.
+const CROSSFADE_SECS: f32 = 1.5;
+/// Volume of the gameplay music while the game is paused.
+const PAUSED_VOLUME: f32 = 0.4;
+/// Playback speed of the gameplay music when the next round could decide the match.
+const MATCH_POINT_SPEED: f32 = 1.12;
+
+fn direct_music(
+    mut commands: Commands,
+    screen: Res<State<Screen>>,
+    menu: Res<State<Menu>>,
+    match_state: Option<Res<State<MatchState>>>,
+    pause: Res<State<Pause>>,
+    score: Res<MatchScore>,
+    config: Res<MatchConfig>,
+    music_assets: Res<MusicAssets>,
+    mut tracks: Query<(&mut MusicTrack, Option<&AudioSink>)>,
+) {
+    let screen_track = match screen.get() {
+        Screen::Splash => None,
+        Screen::Title | Screen::Lobby | Screen::Results => Some(Track::Menu),
+        Screen::Loading | Screen::Gameplay => Some(Track::Gameplay),
+    };
+    let wanted = match menu.get() {
+        Menu::Credits => Some(Track::Credits),
+        // Other menus open over a screen and keep playing its music.
+        Menu::None | Menu::Main | Menu::ArenaSelect | Menu::Settings | Menu::Pause => screen_track,
+    };
+    let volume = if pause.get().0 { PAUSED_VOLUME } else { 1.0 };
+    // Once a match reaches match point, stay intense until it's over. The score of the last
+    // match is kept around until the next one starts, so only check it during a match.
+    let match_point = match_state.is_some() && score.is_match_point(config.rounds_to_win);
+
+    let mut playing = false;
+    for (mut music_track, sink) in &mut tracks {
+        if Some(music_track.track) == wanted {
+            // Sinks only show up once playback starts, which never happens without an audio
+            // device, so don't wait for one before counting a track as playing.
+            playing = true;
+            music_track.target = volume;
+            if music_track.track == Track::Gameplay
+                && let Some(sink) = sink
+            {
+                let speed = if match_point { MATCH_POINT_SPEED } else { 1.0 };
+                if sink.speed() != speed {
+                    sink.set_speed(speed);
+                }
+            }
+        } else {
+            music_track.target = 0.0;
+        }
+    }
+
+    if let Some(track) = wanted
+        && !playing
+    {
+        let handle = match track {
+            Track::Menu => music_assets.menu.clone(),
+            Track::Gameplay => music_assets.gameplay.clone(),
+            Track::Credits => music_assets.credits.clone(),
+        };
+        commands.spawn((
+            Name::new(format!("{track:?} Music")),
+            MusicTrack {
+                track,
+                target: volume,
+            },
+            VolumeScale(0.0),
+            music(handle),
+        ));
+    }
+}
+
+fn fade_music(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut tracks: Query<(Entity, &MusicTrack, &mut VolumeScale)>,
+) {
+    let step = time.delta_secs() / CROSSFADE_SECS;
+    for (entity, music_track, mut scale) in &mut tracks {
+        if scale.0 == music_track.target {
+            continue;
+        }
+        scale.0 = if scale.0 < music_track.target {
+            (scale.0 + step).min(music_track.target)
+        } else {
+            (scale.0 - step).max(music_track.target)
+        };
+        if scale.0 <= 0.0 && music_track.target <= 0.0 {
+            commands.entity(entity).despawn();
+        }
+    }
+}
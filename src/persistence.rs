@@ -0,0 +1,80 @@
+//! Saving and loading small bits of data that should survive restarts, like settings.
+//!
+//! Data is stored as RON, in the platform's config directory on native builds and in
+//! `localStorage` on the web.
+
+use bevy::prelude::*;
+use serde::{Serialize, de::DeserializeOwned};
+
+const APP_NAME: &str = "sword-ball";
+
+/// Load what was last saved under `key`, if anything.
+pub fn load<T: DeserializeOwned>(key: &str) -> Option<T> {
+    let text = read(key)?;
+    ron::from_str(&text)
+        .inspect_err(|error| warn!("Failed to parse saved {key}: {error}"))
+        .ok()
+}
+
+/// Save `value` under `key`, logging a warning if that fails.
+pub fn save<T: Serialize>(key: &str, value: &T) {
+    match ron::ser::to_string_pretty(value, ron::ser::PrettyConfig::default()) {
+        Ok(text) => write(key, &text),
+        Err(error) => warn!("Failed to serialize {key}: {error}"),
+    }
+}
+
+#[cfg(not(target_family = "wasm"))]
+fn path(key: &str) -> Option<std::path::PathBuf> {
+    use std::{env, path::PathBuf};
+
+    let config_dir = if cfg!(target_os = "windows") {
+        env::var_os("APPDATA").map(PathBuf::from)
+    } else if cfg!(target_os = "macos") {
+        env::var_os("HOME").map(|home| PathBuf::from(home).join("Library/Application Support"))
+    } else {
+        env::var_os("XDG_CONFIG_HOME")
+            .map(PathBuf::from)
+            .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))
+    };
+    Some(config_dir?.join(APP_NAME).join(format!("{key}.ron")))
+}
+
+#[cfg(not(target_family = "wasm"))]
+fn read(key: &str) -> Option<String> {
+    std::fs::read_to_string(path(key)?).ok()
+}
+
+#[cfg(not(target_family = "wasm"))]
+fn write(key: &str, text: &str) {
+    let Some(path) = path(key) else {
+        warn!("Failed to save {key}: no config directory");
+        return;
+    };
+    let result = path
+        .parent()
+        .map_or(Ok(()), std::fs::create_dir_all)
+        .and_then(|()| std::fs::write(&path, text));
+    if let Err(error) = result {
+        warn!("Failed to save {key} to {}: {error}", path.display());
+    }
+}
+
+#[cfg(target_family = "wasm")]
+fn storage() -> Option<web_sys::Storage> {
+    web_sys::window()?.local_storage().ok()?
+}
+
+#[cfg(target_family = "wasm")]
+fn read(key: &str) -> Option<String> {
+    storage()?.get_item(&format!("{APP_NAME}.{key}")).ok()?
+}
+
+#[cfg(target_family = "wasm")]
+fn write(key: &str, text: &str) {
+    let saved = storage()
+        .is_some_and(|storage| storage.set_item(&format!("{APP_NAME}.{key}"), text).is_ok());
+    if !saved {
+        warn!("Failed to save {key} to local storage");
+    }
+}
@@ -3,22 +3,30 @@
 use avian2d::prelude::*;
 use bevy::{input::common_conditions::input_just_pressed, prelude::*};
 
-use crate::{Pause, menus::Menu, screens::Screen};
+use crate::{
+    Pause,
+    duel::control::player_gamepad_disconnected,
+    menus::{Menu, not_rebinding},
+    screens::Screen,
+};
 
 pub(super) fn plugin(app: &mut App) {
-    // Toggle pause on key press.
+    // Toggle pause on key press, or when a player's gamepad disconnects.
     app.add_systems(
         Update,
         (
             (pause, spawn_pause_overlay, open_pause_menu).run_if(
-                in_state(Screen::Gameplay)
-                    .and(in_state(Menu::None))
-                    .and(input_just_pressed(KeyCode::KeyP).or(input_just_pressed(KeyCode::Escape))),
+                in_state(Screen::Gameplay).and(in_state(Menu::None)).and(
+                    input_just_pressed(KeyCode::KeyP)
+                        .or(input_just_pressed(KeyCode::Escape))
+                        .or(player_gamepad_disconnected),
+                ),
             ),
             close_menu.run_if(
                 in_state(Screen::Gameplay)
                     .and(not(in_state(Menu::None)))
-                    .and(input_just_pressed(KeyCode::KeyP)),
+                    .and(input_just_pressed(KeyCode::KeyP))
+                    .and(not_rebinding),
             ),
         ),
     );
@@ -0,0 +1,566 @@
+//! The lobby where local players join, pick a color and weapon, and ready up.
+//!
+//! Each half of the keyboard and every gamepad can join with its confirm button, and one player
+//! can join with the mouse or a touch screen through a button. Once at least two players have
+//! joined and all of them are ready, the match starts.
+
+use bevy::{
+    input::{common_conditions::input_just_pressed, gamepad::GamepadConnectionEvent},
+    prelude::*,
+};
+
+use crate::{
+    AppSystems,
+    asset_tracking::ResourceHandles,
+    duel::{
+        ability::{AbilityAssets, AbilitySet},
+        bindings::{
+            Binding, Control, ControlProfile, GAMEPAD_BACK, KEYBOARD_LEFT_BACK,
+            KEYBOARD_RIGHT_BACK, Profile,
+        },
+        control::InputDevice,
+        player::{MAX_PLAYERS, MatchConfig, PLAYER_COLORS, PlayerConfig},
+        weapon::WeaponKind,
+    },
+    screens::Screen,
+    settings::Settings,
+    theme::widget,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<Lobby>();
+    app.init_resource::<PointerSeatInput>();
+    app.add_systems(OnEnter(Screen::Lobby), reset_lobby);
+    app.add_systems(
+        Update,
+        (
+            (
+                remove_disconnected_seats.run_if(on_message::<GamepadConnectionEvent>),
+                update_seats,
+            )
+                .chain()
+                .in_set(AppSystems::RecordInput),
+            (
+                start_match,
+                spawn_lobby_ui
+                    .run_if(resource_changed::<Lobby>.or(resource_added::<AbilityAssets>)),
+            )
+                .in_set(AppSystems::Update),
+            leave_lobby.run_if(input_just_pressed(KeyCode::Escape)),
+        )
+            .run_if(in_state(Screen::Lobby)),
+    );
+}
+
+/// The players that have joined so far, in [`PlayerID`](crate::duel::player::PlayerID) order.
+#[derive(Resource, Debug, Default)]
+struct Lobby {
+    seats: Vec<Seat>,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Seat {
+    device: InputDevice,
+    /// Index into [`PLAYER_COLORS`].
+    color: usize,
+    /// Index into [`WeaponKind::ALL`].
+    weapon: usize,
+    /// Index into the abilities of [`AbilityAssets`].
+    ability: usize,
+    ready: bool,
+}
+
+impl Lobby {
+    fn join(&mut self, device: InputDevice) {
+        if self.seats.len() >= MAX_PLAYERS {
+            return;
+        }
+        let Some(color) = self.free_color(PLAYER_COLORS.len() - 1, 1) else {
+            return;
+        };
+        self.seats.push(Seat {
+            device,
+            color,
+            weapon: 0,
+            ability: 0,
+            ready: false,
+        });
+    }
+
+    /// The next color after `from` in the direction of `step` that nobody else has picked.
+    fn free_color(&self, from: usize, step: isize) -> Option<usize> {
+        let count = PLAYER_COLORS.len() as isize;
+        (1..=count)
+            .map(|offset| (from as isize + offset * step).rem_euclid(count) as usize)
+            .find(|&color| self.seats.iter().all(|seat| seat.color != color) || color == from)
+    }
+
+    fn everyone_ready(&self) -> bool {
+        self.seats.len() >= 2 && self.seats.iter().all(|seat| seat.ready)
+    }
+}
+
+/// Lobby buttons pressed on a single device this frame.
+#[derive(Debug, Default, Clone, Copy)]
+struct SeatInput {
+    previous_color: bool,
+    next_color: bool,
+    previous_weapon: bool,
+    next_weapon: bool,
+    previous_ability: bool,
+    next_ability: bool,
+    confirm: bool,
+    back: bool,
+}
+
+/// Lobby buttons clicked for the mouse/touch seat since the seats were last updated.
+#[derive(Resource, Debug, Default)]
+struct PointerSeatInput(SeatInput);
+
+impl SeatInput {
+    /// Seats navigate with the bindings of their player: moving picks a color and weapon, and
+    /// Fire and Ability cycle through abilities. Backing out isn't a control, so it's passed in.
+    fn from_profile(
+        profile: &Profile,
+        just_pressed: impl Fn(Binding) -> bool,
+        confirm: Control,
+        back: bool,
+    ) -> Self {
+        let pressed = |control| profile.get(control).is_some_and(&just_pressed);
+        Self {
+            previous_color: pressed(Control::MoveLeft),
+            next_color: pressed(Control::MoveRight),
+            previous_weapon: pressed(Control::MoveUp),
+            next_weapon: pressed(Control::MoveDown),
+            previous_ability: pressed(Control::Fire),
+            next_ability: pressed(Control::Ability),
+            confirm: pressed(confirm),
+            back,
+        }
+    }
+
+    fn from_keys(keys: &ButtonInput<KeyCode>, profile: &Profile, back: KeyCode) -> Self {
+        Self::from_profile(
+            profile,
+            |binding| matches!(binding, Binding::Key(key) if keys.just_pressed(key)),
+            KEYBOARD_CONFIRM,
+            keys.just_pressed(back),
+        )
+    }
+
+    fn from_gamepad(gamepad: &Gamepad, profile: &Profile) -> Self {
+        Self::from_profile(
+            profile,
+            |binding| matches!(binding, Binding::Button(button) if gamepad.just_pressed(button)),
+            GAMEPAD_CONFIRM,
+            gamepad.just_pressed(GAMEPAD_BACK),
+        )
+    }
+}
+
+/// The control keyboard seats join and ready up with.
+const KEYBOARD_CONFIRM: Control = Control::Guard;
+/// The control gamepad seats join and ready up with, as it's on the bottom face button by
+/// default.
+const GAMEPAD_CONFIRM: Control = Control::Dash;
+
+/// The gamepad profile of the seat taken by `device`, or of the next seat to be taken.
+fn gamepad_profile(lobby: &Lobby, device: Option<InputDevice>) -> ControlProfile {
+    let index = lobby
+        .seats
+        .iter()
+        .position(|seat| Some(seat.device) == device)
+        .unwrap_or(lobby.seats.len());
+    ControlProfile::Gamepad(index.min(MAX_PLAYERS - 1))
+}
+
+fn reset_lobby(mut commands: Commands) {
+    commands.insert_resource(Lobby::default());
+}
+
+/// A disconnected gamepad can't leave or ready up, so free its seat.
+fn remove_disconnected_seats(
+    mut connections: MessageReader<GamepadConnectionEvent>,
+    mut lobby: ResMut<Lobby>,
+) {
+    for event in connections.read() {
+        if event.disconnected() {
+            lobby
+                .seats
+                .retain(|seat| seat.device != InputDevice::Gamepad(event.gamepad));
+        }
+    }
+}
+
+fn update_seats(
+    keys: Res<ButtonInput<KeyCode>>,
+    settings: Res<Settings>,
+    gamepads: Query<(Entity, &Gamepad)>,
+    mut pointer_input: ResMut<PointerSeatInput>,
+    mut lobby: ResMut<Lobby>,
+    ability_assets: Option<Res<AbilityAssets>>,
+    ability_sets: Res<Assets<AbilitySet>>,
+) {
+    let ability_count = ability_assets.map_or(0, |assets| assets.abilities(&ability_sets).len());
+    let inputs = [
+        (
+            InputDevice::KeyboardLeft,
+            SeatInput::from_keys(
+                &keys,
+                settings.bindings.profile(ControlProfile::KeyboardLeft),
+                KEYBOARD_LEFT_BACK,
+            ),
+        ),
+        (
+            InputDevice::KeyboardRight,
+            SeatInput::from_keys(
+                &keys,
+                settings.bindings.profile(ControlProfile::KeyboardRight),
+                KEYBOARD_RIGHT_BACK,
+            ),
+        ),
+    ]
+    .into_iter()
+    .chain(gamepads.iter().map(|(entity, gamepad)| {
+        let device = InputDevice::Gamepad(entity);
+        let profile = settings
+            .bindings
+            .profile(gamepad_profile(&lobby, Some(device)));
+        (device, SeatInput::from_gamepad(gamepad, profile))
+    }))
+    .chain([(InputDevice::Pointer, std::mem::take(&mut pointer_input.0))])
+    .collect::<Vec<_>>();
+
+    for (device, input) in inputs {
+        let Some(index) = lobby.seats.iter().position(|seat| seat.device == device) else {
+            if input.confirm {
+                lobby.join(device);
+            }
+            continue;
+        };
+
+        let seat = lobby.seats[index];
+        if seat.ready {
+            if input.back {
+                lobby.seats[index].ready = false;
+            }
+            continue;
+        }
+        if input.back {
+            lobby.seats.remove(index);
+            continue;
+        }
+
+        let color_step = input.next_color as isize - input.previous_color as isize;
+        let weapon_step = input.next_weapon as isize - input.previous_weapon as isize;
+        if color_step != 0
+            && let Some(color) = lobby.free_color(seat.color, color_step)
+        {
+            lobby.seats[index].color = color;
+        }
+        if weapon_step != 0 {
+            let count = WeaponKind::ALL.len() as isize;
+            lobby.seats[index].weapon =
+                (seat.weapon as isize + weapon_step).rem_euclid(count) as usize;
+        }
+        let ability_step = input.next_ability as isize - input.previous_ability as isize;
+        if ability_step != 0 && ability_count > 0 {
+            lobby.seats[index].ability =
+                (seat.ability as isize + ability_step).rem_euclid(ability_count as isize) as usize;
+        }
+        if input.confirm {
+            lobby.seats[index].ready = true;
+        }
+    }
+}
+
+fn start_match(
+    lobby: Res<Lobby>,
+    mut config: ResMut<MatchConfig>,
+    resource_handles: Res<ResourceHandles>,
+    mut next_screen: ResMut<NextState<Screen>>,
+) {
+    if !lobby.everyone_ready() {
+        return;
+    }
+    config.players = lobby
+        .seats
+        .iter()
+        .map(|seat| PlayerConfig {
+            color: PLAYER_COLORS[seat.color],
+            weapon: WeaponKind::ALL[seat.weapon],
+            ability: seat.ability,
+            device: seat.device,
+        })
+        .collect();
+    if resource_handles.is_all_done() {
+        next_screen.set(Screen::Gameplay);
+    } else {
+        next_screen.set(Screen::Loading);
+    }
+}
+
+#[derive(Component)]
+struct LobbyUi;
+
+const EMPTY_SEAT_BACKGROUND: Color = Color::srgba(1.0, 1.0, 1.0, 0.05);
+
+fn spawn_lobby_ui(
+    mut commands: Commands,
+    lobby: Res<Lobby>,
+    ui: Query<Entity, With<LobbyUi>>,
+    ability_assets: Option<Res<AbilityAssets>>,
+    ability_sets: Res<Assets<AbilitySet>>,
+    settings: Res<Settings>,
+) {
+    for entity in &ui {
+        commands.entity(entity).despawn();
+    }
+
+    let abilities = ability_assets.map_or(&[][..], |assets| assets.abilities(&ability_sets));
+    let seats = (0..MAX_PLAYERS)
+        .map(|index| {
+            let seat = lobby.seats.get(index);
+            let ability = seat
+                .and_then(|seat| abilities.get(seat.ability))
+                .map_or("No ability", |ability| ability.name.as_str());
+            seat_card(index, seat, ability)
+        })
+        .collect::<Vec<_>>();
+    let pointer_button = match lobby
+        .seats
+        .iter()
+        .find(|seat| seat.device == InputDevice::Pointer)
+    {
+        None => "Join with Mouse/Touch",
+        Some(seat) if seat.ready => "Not Ready",
+        Some(_) => "Ready",
+    };
+    let left = settings.bindings.profile(ControlProfile::KeyboardLeft);
+    let right = settings.bindings.profile(ControlProfile::KeyboardRight);
+    // Gamepad players may have bindings of their own, so show those of the next one to join.
+    let pad = settings.bindings.profile(gamepad_profile(&lobby, None));
+    let status = if lobby.everyone_ready() {
+        "Starting..."
+    } else if lobby.seats.len() < 2 {
+        "Waiting for players to join"
+    } else {
+        "Waiting for everyone to ready up"
+    };
+    commands.spawn((
+        widget::ui_root("Lobby"),
+        LobbyUi,
+        DespawnOnExit(Screen::Lobby),
+        children![
+            widget::header("Lobby"),
+            (
+                Name::new("Seats"),
+                Node {
+                    column_gap: px(20),
+                    ..default()
+                },
+                Children::spawn(SpawnIter(seats.into_iter())),
+            ),
+            widget::label(format!(
+                "Color: {}, {} or {}",
+                keys(left, [Control::MoveLeft, Control::MoveRight]),
+                keys(right, [Control::MoveLeft, Control::MoveRight]),
+                keys(pad, [Control::MoveLeft, Control::MoveRight]),
+            )),
+            widget::label(format!(
+                "Weapon: {}, {} or {}",
+                keys(left, [Control::MoveUp, Control::MoveDown]),
+                keys(right, [Control::MoveUp, Control::MoveDown]),
+                keys(pad, [Control::MoveUp, Control::MoveDown]),
+            )),
+            widget::label(format!(
+                "Ability: {}, {} or {}",
+                keys(left, [Control::Fire, Control::Ability]),
+                keys(right, [Control::Fire, Control::Ability]),
+                keys(pad, [Control::Fire, Control::Ability]),
+            )),
+            widget::label(format!(
+                "Join and ready: {}, {} or {}. Back: {}, {} or {}",
+                key(left, KEYBOARD_CONFIRM),
+                key(right, KEYBOARD_CONFIRM),
+                key(pad, GAMEPAD_CONFIRM),
+                Binding::Key(KEYBOARD_LEFT_BACK),
+                Binding::Key(KEYBOARD_RIGHT_BACK),
+                Binding::Button(GAMEPAD_BACK),
+            )),
+            widget::label(status),
+            widget::button(pointer_button, pointer_seat_on_click),
+            widget::button("Back", leave_lobby_on_click),
+        ],
+    ));
+}
+
+fn seat_card(index: usize, seat: Option<&Seat>, ability: &str) -> impl Bundle {
+    // Only the mouse/touch seat has no device buttons to change its choices with.
+    let pointer = seat.is_some_and(|seat| seat.device == InputDevice::Pointer);
+    let can_leave = pointer && seat.is_some_and(|seat| !seat.ready);
+    let (title, device, color, weapon, ability, status) = match seat {
+        Some(seat) => (
+            format!("Player {}", index + 1),
+            match seat.device {
+                InputDevice::KeyboardLeft => ControlProfile::KeyboardLeft.label(),
+                InputDevice::KeyboardRight => ControlProfile::KeyboardRight.label(),
+                InputDevice::Gamepad(_) => "Gamepad",
+                InputDevice::Pointer => "Mouse / Touch",
+            },
+            PLAYER_COLORS[seat.color],
+            if pointer {
+                format!("{:?}", WeaponKind::ALL[seat.weapon])
+            } else {
+                format!("< {:?} >", WeaponKind::ALL[seat.weapon])
+            },
+            ability.to_string(),
+            if seat.ready { "Ready!" } else { "Not ready" },
+        ),
+        None => (
+            "Open".to_string(),
+            "",
+            Color::NONE,
+            String::new(),
+            String::new(),
+            "Press to join",
+        ),
+    };
+    (
+        Name::new("Seat"),
+        Node {
+            width: px(220),
+            min_height: px(300),
+            padding: UiRect::vertical(px(10)),
+            row_gap: px(6),
+            flex_direction: FlexDirection::Column,
+            align_items: AlignItems::Center,
+            justify_content: JustifyContent::SpaceEvenly,
+            ..default()
+        },
+        BackgroundColor(EMPTY_SEAT_BACKGROUND),
+        BorderRadius::all(px(12)),
+        children![
+            widget::label(title),
+            widget::label(device),
+            seat_row(
+                pointer,
+                (
+                    Name::new("Color Swatch"),
+                    Node {
+                        width: px(60),
+                        height: px(60),
+                        ..default()
+                    },
+                    BackgroundColor(color),
+                    BorderRadius::MAX,
+                ),
+                |input| input.previous_color = true,
+                |input| input.next_color = true,
+            ),
+            seat_row(
+                pointer,
+                widget::label(weapon),
+                |input| input.previous_weapon = true,
+                |input| input.next_weapon = true,
+            ),
+            seat_row(
+                pointer,
+                widget::label(ability),
+                |input| input.previous_ability = true,
+                |input| input.next_ability = true,
+            ),
+            widget::label(status),
+            (
+                Name::new("Leave Seat"),
+                Node {
+                    display: if can_leave {
+                        Display::Flex
+                    } else {
+                        Display::None
+                    },
+                    ..default()
+                },
+                children![widget::button_medium(
+                    "Leave",
+                    press_pointer_seat(|input| input.back = true),
+                )],
+            ),
+        ],
+    )
+}
+
+/// What `control` is bound to in `profile`, for help text.
+fn key(profile: &Profile, control: Control) -> String {
+    profile
+        .get(control)
+        .map_or("Unbound".to_string(), |binding| binding.to_string())
+}
+
+/// The bindings of a previous/next pair of controls.
+fn keys(profile: &Profile, [previous, next]: [Control; 2]) -> String {
+    format!("{}/{}", key(profile, previous), key(profile, next))
+}
+
+/// One of the choices on a seat card, with previous/next buttons on the mouse/touch seat.
+fn seat_row(
+    pointer: bool,
+    content: impl Bundle,
+    previous: fn(&mut SeatInput),
+    next: fn(&mut SeatInput),
+) -> impl Bundle {
+    let arrow = move |text: &'static str, press: fn(&mut SeatInput)| {
+        (
+            Name::new("Seat Arrow"),
+            Node {
+                display: if pointer {
+                    Display::Flex
+                } else {
+                    Display::None
+                },
+                ..default()
+            },
+            children![widget::button_small(text, press_pointer_seat(press))],
+        )
+    };
+    (
+        Name::new("Seat Row"),
+        Node {
+            align_items: AlignItems::Center,
+            column_gap: px(10),
+            ..default()
+        },
+        children![arrow("<", previous), content, arrow(">", next)],
+    )
+}
+
+/// Press a lobby button for the mouse/touch seat, as that has no buttons of its own.
+fn press_pointer_seat(
+    press: fn(&mut SeatInput),
+) -> impl Fn(On<Pointer<Click>>, ResMut<PointerSeatInput>) {
+    move |_, mut input| press(&mut input.0)
+}
+
+/// Join, ready up or unready the mouse/touch seat.
+fn pointer_seat_on_click(
+    _: On<Pointer<Click>>,
+    lobby: Res<Lobby>,
+    mut input: ResMut<PointerSeatInput>,
+) {
+    match lobby
+        .seats
+        .iter()
+        .find(|seat| seat.device == InputDevice::Pointer)
+    {
+        Some(seat) if seat.ready => input.0.back = true,
+        _ => input.0.confirm = true,
+    }
+}
+
+fn leave_lobby_on_click(_: On<Pointer<Click>>, mut next_screen: ResMut<NextState<Screen>>) {
+    next_screen.set(Screen::Title);
+}
+
+fn leave_lobby(mut next_screen: ResMut<NextState<Screen>>) {
+    next_screen.set(Screen::Title);
+}
@@ -2,6 +2,8 @@
 
 mod gameplay;
 mod loading;
+mod lobby;
+mod results;
 mod splash;
 mod title;
 
@@ -13,6 +15,8 @@ pub(super) fn plugin(app: &mut App) {
     app.add_plugins((
         gameplay::plugin,
         loading::plugin,
+        lobby::plugin,
+        results::plugin,
         splash::plugin,
         title::plugin,
     ));
@@ -24,6 +28,8 @@ pub enum Screen {
     #[default]
     Splash,
     Title,
+    Lobby,
     Loading,
     Gameplay,
+    Results,
 }
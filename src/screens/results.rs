@@ -0,0 +1,183 @@
+//! The results screen shown after a match, with placements and stats for every player.
+//!
+//! From here, players can have a rematch, pick another arena for the same roster, or return to
+//! the title screen.
+
+use bevy::prelude::*;
+
+use crate::{
+    duel::{
+        match_state::MatchScore,
+        player::{MatchConfig, PlayerID},
+        stats::{MatchStats, PlayerStats},
+    },
+    menus::Menu,
+    screens::Screen,
+    theme::{palette::*, prelude::*},
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(OnEnter(Screen::Results), spawn_results_screen);
+    app.add_systems(OnExit(Screen::Results), close_menu);
+    app.add_systems(
+        Update,
+        hide_behind_menus.run_if(in_state(Screen::Results).and(state_changed::<Menu>)),
+    );
+}
+
+#[derive(Component)]
+struct ResultsScreen;
+
+const COLUMNS: [&str; 8] = [
+    "Place",
+    "Player",
+    "Wins",
+    "Hits",
+    "Damage",
+    "Ring-outs",
+    "Dashes",
+    "Time Alive",
+];
+
+fn spawn_results_screen(
+    mut commands: Commands,
+    config: Res<MatchConfig>,
+    score: Res<MatchScore>,
+    stats: Res<MatchStats>,
+) {
+    let wins = |PlayerID(index): PlayerID| score.wins.get(index).copied().unwrap_or(0);
+    let mut placings = (0..config.players.len()).map(PlayerID).collect::<Vec<_>>();
+    placings.sort_by_key(|&id| std::cmp::Reverse(wins(id)));
+
+    commands
+        .spawn((
+            widget::ui_root("Results Screen"),
+            ResultsScreen,
+            DespawnOnExit(Screen::Results),
+        ))
+        .with_children(|parent| {
+            parent.spawn(widget::header("Results"));
+            parent
+                .spawn((
+                    Name::new("Results Table"),
+                    Node {
+                        display: Display::Grid,
+                        grid_template_columns: RepeatedGridTrack::auto(COLUMNS.len() as u16),
+                        column_gap: px(24),
+                        row_gap: px(8),
+                        padding: UiRect::all(px(16)),
+                        ..default()
+                    },
+                    BackgroundColor(PANEL_BACKGROUND),
+                    BorderRadius::all(px(8)),
+                ))
+                .with_children(|table| {
+                    for column in COLUMNS {
+                        table.spawn(widget::caption(column));
+                    }
+                    for &id in &placings {
+                        // Players with as many wins share a place.
+                        let place = 1 + placings
+                            .iter()
+                            .filter(|&&other| wins(other) > wins(id))
+                            .count();
+                        let color = config.players[id.0].color;
+                        let player_stats = stats.players.get(id.0).cloned().unwrap_or_default();
+
+                        table.spawn(widget::label(ordinal(place)));
+                        table.spawn(player_name(id, color));
+                        table.spawn(widget::label(wins(id).to_string()));
+                        for value in stat_values(&player_stats) {
+                            table.spawn(widget::label(value));
+                        }
+                    }
+                });
+            parent.spawn((
+                Name::new("Results Buttons"),
+                Node {
+                    column_gap: px(20),
+                    ..default()
+                },
+                children![
+                    widget::button_medium("Rematch", rematch),
+                    widget::button_medium("Change Arena", change_arena),
+                    widget::button_medium("Return to Title", return_to_title),
+                ],
+            ));
+        });
+}
+
+fn player_name(PlayerID(index): PlayerID, color: Color) -> impl Bundle {
+    (
+        Name::new("Player Name"),
+        Node {
+            align_items: AlignItems::Center,
+            column_gap: px(8),
+            ..default()
+        },
+        children![
+            (
+                Name::new("Player Color"),
+                Node {
+                    width: px(16),
+                    height: px(16),
+                    ..default()
+                },
+                BackgroundColor(color),
+                BorderRadius::MAX,
+            ),
+            widget::label(format!("P{}", index + 1)),
+        ],
+    )
+}
+
+fn stat_values(stats: &PlayerStats) -> [String; 5] {
+    [
+        stats.hits_landed.to_string(),
+        format!("{:.0}%", stats.damage_dealt),
+        stats.ring_outs.to_string(),
+        stats.dashes_used.to_string(),
+        format!("{:.1}s", stats.time_alive),
+    ]
+}
+
+fn ordinal(place: usize) -> String {
+    let suffix = match place {
+        1 => "st",
+        2 => "nd",
+        3 => "rd",
+        _ => "th",
+    };
+    format!("{place}{suffix}")
+}
+
+/// The arena select menu opens over this screen, so get out of its way.
+fn hide_behind_menus(
+    menu: Res<State<Menu>>,
+    mut screens: Query<&mut Visibility, With<ResultsScreen>>,
+) {
+    for mut visibility in &mut screens {
+        *visibility = if *menu.get() == Menu::None {
+            Visibility::Inherited
+        } else {
+            Visibility::Hidden
+        };
+    }
+}
+
+/// Fight again with the same players in the same arena.
+fn rematch(_: On<Pointer<Click>>, mut next_screen: ResMut<NextState<Screen>>) {
+    next_screen.set(Screen::Loading);
+}
+
+fn change_arena(_: On<Pointer<Click>>, mut next_menu: ResMut<NextState<Menu>>) {
+    next_menu.set(Menu::ArenaSelect);
+}
+
+fn return_to_title(_: On<Pointer<Click>>, mut next_screen: ResMut<NextState<Screen>>) {
+    next_screen.set(Screen::Title);
+}
+
+fn close_menu(mut next_menu: ResMut<NextState<Menu>>) {
+    next_menu.set(Menu::None);
+}
@@ -0,0 +1,123 @@
+//! Player settings that persist between launches.
+//!
+//! Settings are loaded on startup, before the first screen shows up, and saved when leaving
+//! the settings menu.
+
+use bevy::{
+    audio::Volume,
+    prelude::*,
+    window::{PresentMode, PrimaryWindow, WindowMode},
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    audio::{CategoryVolume, MusicVolume, SoundEffectVolume, UiVolume},
+    duel::bindings::Bindings,
+    persistence,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.insert_resource(persistence::load::<Settings>(Settings::KEY).unwrap_or_default());
+    app.add_systems(PreStartup, apply_settings);
+    app.add_systems(Update, apply_settings.run_if(resource_changed::<Settings>));
+}
+
+#[derive(Resource, Clone, Debug, Serialize, Deserialize, Reflect)]
+#[reflect(Resource)]
+#[serde(default)]
+pub struct Settings {
+    /// Linear master volume.
+    pub volume: f32,
+    pub music: CategoryVolume,
+    pub sound_effects: CategoryVolume,
+    pub ui_sounds: CategoryVolume,
+    pub bindings: Bindings,
+    pub display: DisplaySettings,
+    pub accessibility: AccessibilitySettings,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            volume: 1.0,
+            music: CategoryVolume::default(),
+            sound_effects: CategoryVolume::default(),
+            ui_sounds: CategoryVolume::default(),
+            bindings: Bindings::default(),
+            display: DisplaySettings::default(),
+            accessibility: AccessibilitySettings::default(),
+        }
+    }
+}
+
+impl Settings {
+    /// Where settings are saved, see [`persistence`].
+    pub const KEY: &str = "settings";
+
+    pub fn save(&self) {
+        persistence::save(Self::KEY, self);
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, Reflect)]
+#[serde(default)]
+pub struct DisplaySettings {
+    pub fullscreen: bool,
+    pub vsync: bool,
+}
+
+impl Default for DisplaySettings {
+    fn default() -> Self {
+        Self {
+            fullscreen: false,
+            vsync: true,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, Reflect)]
+#[serde(default)]
+pub struct AccessibilitySettings {
+    /// Scale of all UI, on top of the window's scale factor.
+    pub ui_scale: f32,
+}
+
+impl Default for AccessibilitySettings {
+    fn default() -> Self {
+        Self { ui_scale: 1.0 }
+    }
+}
+
+fn apply_settings(
+    settings: Res<Settings>,
+    mut global_volume: ResMut<GlobalVolume>,
+    mut music_volume: ResMut<MusicVolume>,
+    mut sound_effect_volume: ResMut<SoundEffectVolume>,
+    mut ui_volume: ResMut<UiVolume>,
+    mut ui_scale: ResMut<UiScale>,
+    mut window: Single<&mut Window, With<PrimaryWindow>>,
+) {
+    global_volume.volume = Volume::Linear(settings.volume);
+    music_volume.0 = settings.music;
+    sound_effect_volume.0 = settings.sound_effects;
+    ui_volume.0 = settings.ui_sounds;
+    ui_scale.0 = settings.accessibility.ui_scale;
+
+    let mode = if settings.display.fullscreen {
+        WindowMode::BorderlessFullscreen(MonitorSelection::Current)
+    } else {
+        WindowMode::Windowed
+    };
+    let present_mode = if settings.display.vsync {
+        PresentMode::AutoVsync
+    } else {
+        PresentMode::AutoNoVsync
+    };
+    // Only touch the window when needed, as any change to it is sent to the windowing backend.
+    if window.mode != mode {
+        window.mode = mode;
+    }
+    if window.present_mode != present_mode {
+        window.present_mode = present_mode;
+    }
+}
@@ -14,3 +14,8 @@ pub const BUTTON_BACKGROUND: Color = Color::srgb(0.275, 0.400, 0.750);
 pub const BUTTON_HOVERED_BACKGROUND: Color = Color::srgb(0.384, 0.600, 0.820);
 /// #3d4999
 pub const BUTTON_PRESSED_BACKGROUND: Color = Color::srgb(0.239, 0.286, 0.600);
+
+/// #000000 at 50% opacity
+pub const PANEL_BACKGROUND: Color = Color::srgba(0.0, 0.0, 0.0, 0.5);
+/// #ffffff at 15% opacity
+pub const METER_BACKGROUND: Color = Color::srgba(1.0, 1.0, 1.0, 0.15);
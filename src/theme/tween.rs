@@ -0,0 +1,114 @@
+//! Simple animations for UI nodes.
+
+use bevy::prelude::*;
+
+use crate::{AppSystems, PausableSystems};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(
+        Update,
+        (
+            tick_fade_in_out
+                .in_set(AppSystems::TickTimers)
+                .in_set(PausableSystems),
+            (apply_fade_in_out, despawn_finished_fades).in_set(AppSystems::Update),
+        ),
+    );
+}
+
+/// Fades a UI node's image or text in, holds it, then fades it back out.
+///
+/// Optionally, the node also pops in from a different scale, and despawns once it's faded out.
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+pub struct FadeInOut {
+    /// Total duration in seconds.
+    pub total_duration: f32,
+    /// Fade duration in seconds.
+    pub fade_duration: f32,
+    /// Scale the node starts at, easing to its normal size as it fades in.
+    pub pop_scale: f32,
+    /// Whether to despawn the node once it has faded out.
+    pub despawn: bool,
+    /// Current progress in seconds, between 0 and [`Self::total_duration`].
+    pub t: f32,
+}
+
+impl FadeInOut {
+    pub fn new(total_duration: f32, fade_duration: f32) -> Self {
+        Self {
+            total_duration,
+            fade_duration,
+            pop_scale: 1.0,
+            despawn: false,
+            t: 0.0,
+        }
+    }
+
+    /// Start at `scale` times the normal size.
+    pub fn with_pop(mut self, scale: f32) -> Self {
+        self.pop_scale = scale;
+        self
+    }
+
+    /// Despawn once faded out.
+    pub fn despawning(mut self) -> Self {
+        self.despawn = true;
+        self
+    }
+
+    fn alpha(&self) -> f32 {
+        // Normalize by duration.
+        let t = (self.t / self.total_duration).clamp(0.0, 1.0);
+        let fade = self.fade_duration / self.total_duration;
+
+        // Regular trapezoid-shaped graph, flat at the top with alpha = 1.0.
+        ((1.0 - (2.0 * t - 1.0).abs()) / fade).min(1.0)
+    }
+
+    fn scale(&self) -> f32 {
+        let t = (self.t / self.fade_duration).clamp(0.0, 1.0);
+        // Ease out, so the pop slows down as it settles.
+        let eased = 1.0 - (1.0 - t).powi(3);
+        self.pop_scale.lerp(1.0, eased)
+    }
+
+    fn is_finished(&self) -> bool {
+        self.t >= self.total_duration
+    }
+}
+
+fn tick_fade_in_out(time: Res<Time>, mut animation_query: Query<&mut FadeInOut>) {
+    for mut anim in &mut animation_query {
+        anim.t += time.delta_secs();
+    }
+}
+
+fn apply_fade_in_out(
+    mut animation_query: Query<(
+        &FadeInOut,
+        Option<&mut ImageNode>,
+        Option<&mut TextColor>,
+        &mut UiTransform,
+    )>,
+) {
+    for (anim, image, text_color, mut transform) in &mut animation_query {
+        if let Some(mut image) = image {
+            image.color.set_alpha(anim.alpha());
+        }
+        if let Some(mut text_color) = text_color {
+            text_color.0.set_alpha(anim.alpha());
+        }
+        if anim.pop_scale != 1.0 {
+            transform.scale = Vec2::splat(anim.scale());
+        }
+    }
+}
+
+fn despawn_finished_fades(mut commands: Commands, animation_query: Query<(Entity, &FadeInOut)>) {
+    for (entity, anim) in &animation_query {
+        if anim.despawn && anim.is_finished() {
+            commands.entity(entity).despawn();
+        }
+    }
+}
@@ -38,6 +38,16 @@ pub fn header(text: impl Into<String>) -> impl Bundle {
     )
 }
 
+/// A huge label for announcements, such as a round starting. Bigger than [`header`].
+pub fn banner(text: impl Into<String>, color: Color) -> impl Bundle {
+    (
+        Name::new("Banner"),
+        Text(text.into()),
+        TextFont::from_font_size(96.0),
+        TextColor(color),
+    )
+}
+
 /// A simple text label.
 pub fn label(text: impl Into<String>) -> impl Bundle {
     (
@@ -48,6 +58,43 @@ pub fn label(text: impl Into<String>) -> impl Bundle {
     )
 }
 
+/// A small text label, for annotating other widgets.
+pub fn caption(text: impl Into<String>) -> impl Bundle {
+    (
+        Name::new("Caption"),
+        Text(text.into()),
+        TextFont::from_font_size(14.0),
+        TextColor(LABEL_TEXT),
+    )
+}
+
+/// A horizontal bar filled up from the left by its child, which gets `fill` as well.
+///
+/// Set the [`Node::width`] of the fill to a percentage to change how full the meter is.
+pub fn meter(color: Color, fill: impl Bundle) -> impl Bundle {
+    (
+        Name::new("Meter"),
+        Node {
+            width: px(120),
+            height: px(8),
+            ..default()
+        },
+        BackgroundColor(METER_BACKGROUND),
+        BorderRadius::all(px(4)),
+        children![(
+            Name::new("Meter Fill"),
+            Node {
+                width: percent(100),
+                height: percent(100),
+                ..default()
+            },
+            BackgroundColor(color),
+            BorderRadius::all(px(4)),
+            fill,
+        )],
+    )
+}
+
 /// A large rounded button with text and an action defined as an [`Observer`].
 pub fn button<E, B, M, I>(text: impl Into<String>, action: I) -> impl Bundle
 where
@@ -56,7 +103,7 @@ where
     I: IntoObserverSystem<E, B, M>,
 {
     button_base(
-        text,
+        button_text(text, 40.0),
         action,
         (
             Node {
@@ -71,6 +118,29 @@ where
     )
 }
 
+/// A medium-sized rounded button with text and an action defined as an [`Observer`].
+pub fn button_medium<E, B, M, I>(text: impl Into<String>, action: I) -> impl Bundle
+where
+    E: EntityEvent,
+    B: Bundle,
+    I: IntoObserverSystem<E, B, M>,
+{
+    button_base(
+        button_text(text, 24.0),
+        action,
+        (
+            Node {
+                width: px(200),
+                height: px(40),
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::Center,
+                ..default()
+            },
+            BorderRadius::all(px(8)),
+        ),
+    )
+}
+
 /// A small square button with text and an action defined as an [`Observer`].
 pub fn button_small<E, B, M, I>(text: impl Into<String>, action: I) -> impl Bundle
 where
@@ -79,7 +149,7 @@ where
     I: IntoObserverSystem<E, B, M>,
 {
     button_base(
-        text,
+        button_text(text, 40.0),
         action,
         Node {
             width: px(30),
@@ -91,10 +161,68 @@ where
     )
 }
 
-/// A simple button with text and an action defined as an [`Observer`]. The button's layout is provided by `button_bundle`.
-fn button_base<E, B, M, I>(
+/// A button showing an image above a line of text, with an action defined as an [`Observer`].
+pub fn image_button<E, B, M, I>(
+    image: Handle<Image>,
+    image_size: Vec2,
     text: impl Into<String>,
     action: I,
+) -> impl Bundle
+where
+    E: EntityEvent,
+    B: Bundle,
+    I: IntoObserverSystem<E, B, M>,
+{
+    button_base(
+        children![
+            (
+                Name::new("Button Image"),
+                ImageNode::new(image),
+                Node {
+                    width: px(image_size.x),
+                    height: px(image_size.y),
+                    ..default()
+                },
+                Pickable::IGNORE,
+            ),
+            (
+                Name::new("Button Text"),
+                Text(text.into()),
+                TextFont::from_font_size(24.0),
+                TextColor(BUTTON_TEXT),
+                Pickable::IGNORE,
+            ),
+        ],
+        action,
+        (
+            Node {
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Center,
+                padding: UiRect::all(px(10)),
+                row_gap: px(8),
+                ..default()
+            },
+            BorderRadius::all(px(12)),
+        ),
+    )
+}
+
+fn button_text(text: impl Into<String>, font_size: f32) -> impl Bundle {
+    children![(
+        Name::new("Button Text"),
+        Text(text.into()),
+        TextFont::from_font_size(font_size),
+        TextColor(BUTTON_TEXT),
+        // Don't bubble picking events from the text up to the button.
+        Pickable::IGNORE,
+    )]
+}
+
+/// A simple button with an action defined as an [`Observer`]. The button's content and layout
+/// are provided by `content` and `button_bundle`.
+fn button_base<E, B, M, I>(
+    content: impl Bundle,
+    action: I,
     button_bundle: impl Bundle,
 ) -> impl Bundle
 where
@@ -102,7 +230,6 @@ where
     B: Bundle,
     I: IntoObserverSystem<E, B, M>,
 {
-    let text = text.into();
     let action = IntoObserverSystem::into_system(action);
     (
         Name::new("Button"),
@@ -118,14 +245,7 @@ where
                         hovered: BUTTON_HOVERED_BACKGROUND,
                         pressed: BUTTON_PRESSED_BACKGROUND,
                     },
-                    children![(
-                        Name::new("Button Text"),
-                        Text(text),
-                        TextFont::from_font_size(40.0),
-                        TextColor(BUTTON_TEXT),
-                        // Don't bubble picking events from the text up to the button.
-                        Pickable::IGNORE,
-                    )],
+                    content,
                 ))
                 .insert(button_bundle)
                 .observe(action);